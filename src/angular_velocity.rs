@@ -1,7 +1,11 @@
+use core::ops::Deref;
+
 use embedded_hal::digital::InputPin;
 
 use crate::Direction;
 use crate::RotaryEncoder;
+use crate::Sensitivity;
+use crate::TimedMode;
 
 /// Default angular velocity increasing factor
 const DEFAULT_VELOCITY_INC_FACTOR: f32 = 0.2;
@@ -9,8 +13,130 @@ const DEFAULT_VELOCITY_INC_FACTOR: f32 = 0.2;
 const DEFAULT_VELOCITY_DEC_FACTOR: f32 = 0.01;
 /// Angular velocity action window duration in milliseconds
 const DEFAULT_VELOCITY_ACTION_MS: u64 = 25;
-/// Velocity type, the value is between 0.0 and 1.0
-pub type Velocity = f32;
+/// Default quiet duration in milliseconds after the latest step before `settle` fires
+const DEFAULT_SETTLE_MS: u32 = 500;
+/// Default maximum increment yielded by `update_accelerated` at full velocity
+const DEFAULT_ACCEL_MAX: i32 = 10;
+
+/// Default velocity above which `coast` keeps emitting steps
+const DEFAULT_COAST_VELOCITY_MIN: f32 = 0.3;
+
+/// Steepness of the `AccelerationCurve::Exponential` curve
+const EXPONENTIAL_CURVE_STEEPNESS: f32 = 4.0;
+
+/// Relative tolerance between the polynomial and `micromath` `e^x` over the curve's range
+#[cfg(all(test, feature = "micromath"))]
+const EXP_TOLERANCE: f32 = 1e-2;
+
+/// Shape of the mapping from velocity to the increment yielded by `update_accelerated`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AccelerationCurve {
+    /// The increment grows in proportion to the velocity
+    Linear,
+    /// The increment stays small at low velocities and grows rapidly towards full velocity.
+    /// `e^x` comes from `micromath` with the `micromath` feature, otherwise from a polynomial approximation
+    /// which stays within 1% of it
+    Exponential,
+}
+
+impl AccelerationCurve {
+    /// Map a velocity between 0.0 and 1.0 onto the curve, the result is also between 0.0 and 1.0
+    pub fn apply(self, velocity: f32) -> f32 {
+        match self {
+            AccelerationCurve::Linear => velocity,
+            AccelerationCurve::Exponential => {
+                (exp(EXPONENTIAL_CURVE_STEEPNESS * velocity) - 1.0)
+                    / (exp(EXPONENTIAL_CURVE_STEEPNESS) - 1.0)
+            }
+        }
+    }
+}
+
+/// `e^x` from `micromath`, called through the trait so `std` never shadows it
+#[cfg(feature = "micromath")]
+fn exp(x: f32) -> f32 {
+    micromath::F32Ext::exp(x)
+}
+
+/// `e^x` from the polynomial approximation, as `core` has no float math
+#[cfg(not(feature = "micromath"))]
+fn exp(x: f32) -> f32 {
+    exp_polynomial(x)
+}
+
+/// Polynomial approximation of `e^x` for small non-negative `x`.
+/// The argument is divided by 16, a 5th order Taylor series is applied and the result squared 4 times.
+#[cfg(any(test, not(feature = "micromath")))]
+fn exp_polynomial(x: f32) -> f32 {
+    let r = x / 16.0;
+    let mut y = 1.0 + r * (1.0 + r * (0.5 + r * (1.0 / 6.0 + r * (1.0 / 24.0 + r / 120.0))));
+    for _ in 0..4 {
+        y *= y;
+    }
+    y
+}
+
+/// Approximation of the natural logarithm for positive `x`, as `core` has no float math.
+/// `x` is split into `m * 2^e` with `m` in 1.0..2.0, `ln(m)` is taken from the atanh series.
+fn ln(x: f32) -> f32 {
+    let bits = x.to_bits();
+    let e = ((bits >> 23) & 0xFF) as i32 - 127;
+    let m = f32::from_bits((bits & 0x007F_FFFF) | 0x3F80_0000);
+    let s = (m - 1.0) / (m + 1.0);
+    let s2 = s * s;
+    let ln_m = 2.0 * s * (1.0 + s2 * (1.0 / 3.0 + s2 * (1.0 / 5.0 + s2 * (1.0 / 7.0 + s2 / 9.0))));
+    e as f32 * core::f32::consts::LN_2 + ln_m
+}
+
+/// Velocity type, the value is clamped between 0.0 and 1.0
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Velocity(f32);
+
+impl Velocity {
+    /// Initialises a Velocity, saturating `value` to 0.0..=1.0. NaN becomes 0.0
+    pub fn new(value: f32) -> Self {
+        if value >= 1.0 {
+            Velocity(1.0)
+        } else if value > 0.0 {
+            Velocity(value)
+        } else {
+            Velocity(0.0)
+        }
+    }
+
+    /// Returns the velocity as a plain `f32`
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+
+    /// Returns the velocity raised to `gamma`, a perceptual mapping for output rather than acceleration.
+    /// A `gamma` above 1.0 keeps slow spins finer, below 1.0 makes them coarser. The result is between 0.0 and 1.0,
+    /// a `gamma` of 0.0 or below yields 1.0
+    pub fn curved(self, gamma: f32) -> f32 {
+        if gamma <= 0.0 {
+            1.0
+        } else if self.0 <= 0.0 {
+            0.0
+        } else {
+            // v^gamma = e^(gamma * ln v), where gamma * ln v <= 0
+            (1.0 / exp(-gamma * ln(self.0))).min(1.0)
+        }
+    }
+}
+
+impl Deref for Velocity {
+    type Target = f32;
+
+    fn deref(&self) -> &f32 {
+        &self.0
+    }
+}
+
+impl From<Velocity> for f32 {
+    fn from(velocity: Velocity) -> f32 {
+        velocity.0
+    }
+}
 
 // For debouncing of pins, use 0x0f (b00001111) and 0x0c (b00001100) etc.
 const PIN_MASK: u8 = 0x03;
@@ -18,19 +144,50 @@ const PIN_EDGE: u8 = 0x02;
 
 /// AngularVelocityMode
 /// Uses the full-step table with additional angular-velocity measurement
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct AngularVelocityMode {
     /// The pin state
     pin_state: [u8; 2],
     /// The instantaneous velocity
-    velocity: Velocity,
+    velocity: f32,
     /// The increasing factor
     velocity_inc_factor: f32,
     /// The decreasing factor
     velocity_dec_factor: f32,
     /// The action window
     velocity_action_ms: u64,
+    /// The velocity decay floor
+    velocity_floor: f32,
+    /// The baseline interval for adaptive acceleration, 0 to use the action window
+    velocity_baseline_ms: u64,
     /// The last timestamp in mS
     previous_time_millis: u64,
+    /// The increment yielded at full velocity
+    accel_max: i32,
+    /// The shape of the increment
+    accel_curve: AccelerationCurve,
+    /// The direction of the latest update
+    direction: Direction,
+    /// The timestamp of the latest Clockwise step
+    last_cw_millis: Option<u64>,
+    /// The timestamp of the latest Anticlockwise step
+    last_ccw_millis: Option<u64>,
+    /// The timestamp of the latest step in either direction
+    last_step_millis: Option<u64>,
+    /// The direction of the latest step
+    last_step_direction: Direction,
+    /// The quiet duration before settling
+    settle_ms: u32,
+    /// Whether a step occurred since the last settle
+    settle_pending: bool,
+    /// Whether the velocity resets once the encoder rests on a detent
+    reset_on_detent: bool,
+    /// Whether `coast` emits steps
+    coast_enabled: bool,
+    /// The velocity above which `coast` emits steps
+    coast_velocity_min: f32,
+    /// The timestamp of the latest coast step
+    last_coast_millis: Option<u64>,
 }
 
 impl<DT, CLK> RotaryEncoder<AngularVelocityMode, DT, CLK>
@@ -53,32 +210,115 @@ where
         self.mode.velocity_action_ms = action_ms;
     }
 
+    /// Set the velocity_baseline_ms, see `AngularVelocityMode::set_velocity_baseline_ms`
+    pub fn set_velocity_baseline_ms(&mut self, baseline_ms: u64) {
+        self.mode.set_velocity_baseline_ms(baseline_ms);
+    }
+
+    /// Set the accel_max, see `AngularVelocityMode::set_accel_max`
+    pub fn set_accel_max(&mut self, accel_max: i32) {
+        self.mode.set_accel_max(accel_max);
+    }
+
+    /// Set the accel_curve, see `AngularVelocityMode::set_accel_curve`
+    pub fn set_accel_curve(&mut self, accel_curve: AccelerationCurve) {
+        self.mode.set_accel_curve(accel_curve);
+    }
+
+    /// Set the velocity_inc_factor and velocity_action_ms from a `Sensitivity` preset
+    pub fn apply_sensitivity(&mut self, sensitivity: Sensitivity) {
+        self.mode.apply_sensitivity(sensitivity);
+    }
+
     /// This function should be called periodically, either via a timer or the main loop.
     /// This function will reduce the angular velocity over time, the amount is configurable via the constructor
     pub fn decay_velocity(&mut self) {
-        self.mode.velocity -= self.mode.velocity_dec_factor;
-        if self.mode.velocity < 0.0 {
-            self.mode.velocity = 0.0;
-        }
+        self.mode.decay_velocity();
+    }
+
+    /// Set the velocity_floor, see `AngularVelocityMode::set_velocity_floor`
+    pub fn set_velocity_floor(&mut self, floor: f32) {
+        self.mode.set_velocity_floor(floor);
+    }
+
+    /// Reset the angular velocity to the velocity floor, see `AngularVelocityMode::reset_velocity`
+    pub fn reset_velocity(&mut self) {
+        self.mode.reset_velocity();
     }
 
     /// Update the state machine of the RotaryEncoder. This should be called ideally from an interrupt vector
     /// when either the DT or CLK pins state changes. This function will update the RotaryEncoder's
     /// Direction and current Angular Velocity.
-    /// * `current_time` - Current timestamp in ms (strictly monotonously increasing)
+    /// * `current_time_millis` - Current timestamp in ms. Time going backwards, such as a wrapping timer, counts as zero elapsed
     pub fn update(&mut self, current_time_millis: u64) -> Direction {
-        self.mode.update(
-            self.pin_dt.is_high().unwrap_or_default(),
-            self.pin_clk.is_high().unwrap_or_default(),
-            current_time_millis,
-        )
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk, current_time_millis)
+    }
+
+    /// Update the RotaryEncoder and return the amount to change a value by this tick.
+    /// The increment is 1 at slow speeds and scales with the angular velocity up to `accel_max`,
+    /// it is positive for Clockwise, negative for Anticlockwise and 0 when no step occurred.
+    /// * `current_time_millis` - Current timestamp in ms, as for `update`
+    pub fn update_accelerated(&mut self, current_time_millis: u64) -> i32 {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_accelerated(dt, clk, current_time_millis)
     }
 
     /// Returns the current angular velocity of the RotaryEncoder
     /// The Angular Velocity is a value between 0.0 and 1.0
     /// This is useful for incrementing/decrementing a value in an exponential fashion
     pub fn velocity(&self) -> Velocity {
-        self.mode.velocity
+        self.mode.velocity()
+    }
+
+    /// Returns the angular velocity raised to `gamma`, see `Velocity::curved`
+    pub fn velocity_curved(&self, gamma: f32) -> f32 {
+        self.mode.velocity().curved(gamma)
+    }
+
+    /// Set the reset_on_detent, see `AngularVelocityMode::set_reset_on_detent`
+    pub fn set_reset_on_detent(&mut self, reset_on_detent: bool) {
+        self.mode.set_reset_on_detent(reset_on_detent);
+    }
+
+    /// Set the coast_enabled, see `AngularVelocityMode::set_coast_enabled`
+    pub fn set_coast_enabled(&mut self, coast_enabled: bool) {
+        self.mode.set_coast_enabled(coast_enabled);
+    }
+
+    /// Set the coast_velocity_min, see `AngularVelocityMode::set_coast_velocity_min`
+    pub fn set_coast_velocity_min(&mut self, coast_velocity_min: f32) {
+        self.mode.set_coast_velocity_min(coast_velocity_min);
+    }
+
+    /// Emit a coasting step in the direction of the latest step, see `AngularVelocityMode::coast`
+    pub fn coast(&mut self, current_time_millis: u64) -> Direction {
+        self.mode.coast(current_time_millis)
+    }
+
+    /// Set the settle_ms, see `AngularVelocityMode::set_settle_ms`
+    pub fn set_settle_ms(&mut self, settle_ms: u32) {
+        self.mode.set_settle_ms(settle_ms);
+    }
+
+    /// Returns true once the encoder has been still for `settle_ms`, see `AngularVelocityMode::settle`
+    pub fn settle(&mut self, current_time_millis: u64) -> bool {
+        self.mode.settle(current_time_millis)
+    }
+
+    /// Returns the timestamp of the latest Clockwise step, see `AngularVelocityMode::last_cw_millis`
+    pub fn last_cw_millis(&self) -> Option<u64> {
+        self.mode.last_cw_millis()
+    }
+
+    /// Returns the timestamp of the latest Anticlockwise step, see `AngularVelocityMode::last_ccw_millis`
+    pub fn last_ccw_millis(&self) -> Option<u64> {
+        self.mode.last_ccw_millis()
+    }
+
+    /// Returns the direction of the latest update gated by velocity, see `AngularVelocityMode::directed_above`
+    pub fn directed_above(&self, min_velocity: f32) -> Direction {
+        self.mode.directed_above(min_velocity)
     }
 }
 
@@ -90,12 +330,82 @@ impl AngularVelocityMode {
             velocity: 0.0,
             previous_time_millis: 0,
             velocity_action_ms: DEFAULT_VELOCITY_ACTION_MS,
+            velocity_floor: 0.0,
+            velocity_baseline_ms: 0,
             velocity_dec_factor: DEFAULT_VELOCITY_DEC_FACTOR,
             velocity_inc_factor: DEFAULT_VELOCITY_INC_FACTOR,
+            accel_max: DEFAULT_ACCEL_MAX,
+            accel_curve: AccelerationCurve::Linear,
+            direction: Direction::None,
+            last_cw_millis: None,
+            last_ccw_millis: None,
+            last_step_millis: None,
+            last_step_direction: Direction::None,
+            settle_ms: DEFAULT_SETTLE_MS,
+            settle_pending: false,
+            reset_on_detent: false,
+            coast_enabled: false,
+            coast_velocity_min: DEFAULT_COAST_VELOCITY_MIN,
+            last_coast_millis: None,
         }
     }
 
-    /// Update to determine the direction
+    /// Set the accel_max. The increment yielded by `update_accelerated` at full velocity, clamped to at least 1.
+    pub fn set_accel_max(&mut self, accel_max: i32) {
+        self.accel_max = accel_max.max(1);
+    }
+
+    /// Set the velocity_floor. The resting velocity which `decay_velocity` will not decay below, 0.0 by default.
+    /// Steps inside the action window still increase the velocity from the floor towards 1.0
+    pub fn set_velocity_floor(&mut self, floor: f32) {
+        self.velocity_floor = floor;
+    }
+
+    /// Set the accel_curve. How the increment yielded by `update_accelerated` grows with velocity, linear by default.
+    pub fn set_accel_curve(&mut self, accel_curve: AccelerationCurve) {
+        self.accel_curve = accel_curve;
+    }
+
+    /// Set the settle_ms. The quiet duration (milliseconds) after the latest step before `settle` fires
+    pub fn set_settle_ms(&mut self, settle_ms: u32) {
+        self.settle_ms = settle_ms;
+    }
+
+    /// Set reset_on_detent. When enabled, the velocity drops straight to the velocity floor once an update finds
+    /// no step within the action window after the latest detent, instead of waiting for `decay_velocity` to wind it down.
+    /// Decay still applies as normal while the encoder keeps turning.
+    pub fn set_reset_on_detent(&mut self, reset_on_detent: bool) {
+        self.reset_on_detent = reset_on_detent;
+    }
+
+    /// Set the velocity_baseline_ms. When non-zero the velocity increases in proportion to how much shorter the
+    /// interval between steps is than this baseline, instead of by the full `velocity_inc_factor` inside the action window.
+    /// A step arriving instantly adds the whole factor, one arriving after the baseline adds nothing. 0 restores the action window.
+    pub fn set_velocity_baseline_ms(&mut self, baseline_ms: u64) {
+        self.velocity_baseline_ms = baseline_ms;
+    }
+
+    /// Set coast_enabled. When enabled, `coast` keeps emitting steps after a fast spin stops, disabled by default.
+    pub fn set_coast_enabled(&mut self, coast_enabled: bool) {
+        self.coast_enabled = coast_enabled;
+    }
+
+    /// Set the coast_velocity_min. The velocity above which `coast` keeps emitting steps
+    pub fn set_coast_velocity_min(&mut self, coast_velocity_min: f32) {
+        self.coast_velocity_min = coast_velocity_min;
+    }
+
+    /// Set the velocity_inc_factor and velocity_action_ms from a `Sensitivity` preset
+    pub fn apply_sensitivity(&mut self, sensitivity: Sensitivity) {
+        (self.velocity_inc_factor, self.velocity_action_ms) = match sensitivity {
+            Sensitivity::Low => (0.1, 15),
+            Sensitivity::Medium => (DEFAULT_VELOCITY_INC_FACTOR, DEFAULT_VELOCITY_ACTION_MS),
+            Sensitivity::High => (0.4, 40),
+        };
+    }
+
+    /// Update to determine the direction.
+    /// Time going backwards, from a wrapping timer or a non-monotonic caller, is treated as zero elapsed.
     pub fn update(
         &mut self,
         dt_state: bool,
@@ -117,20 +427,145 @@ impl AngularVelocityMode {
         }
 
         if dir != Direction::None {
-            if current_time_millis - self.previous_time_millis < self.velocity_action_ms
-                && self.velocity < 1.0
-            {
-                self.velocity += self.velocity_inc_factor;
-                if self.velocity > 1.0 {
-                    self.velocity = 1.0;
+            let elapsed_millis = current_time_millis.saturating_sub(self.previous_time_millis);
+            if self.velocity_baseline_ms > 0 {
+                if elapsed_millis < self.velocity_baseline_ms {
+                    let ratio = (self.velocity_baseline_ms - elapsed_millis) as f32
+                        / self.velocity_baseline_ms as f32;
+                    self.velocity += self.velocity_inc_factor * ratio;
                 }
+            } else if elapsed_millis < self.velocity_action_ms {
+                self.velocity += self.velocity_inc_factor;
+            }
+            if self.velocity > 1.0 {
+                self.velocity = 1.0;
             }
         } else {
             self.previous_time_millis = current_time_millis;
+
+            if let Some(last_step_millis) = self.last_step_millis {
+                if self.reset_on_detent
+                    && current_time_millis.saturating_sub(last_step_millis)
+                        >= self.velocity_action_ms
+                {
+                    self.velocity = self.velocity_floor;
+                }
+            }
+        }
+
+        match dir {
+            Direction::Clockwise => self.last_cw_millis = Some(current_time_millis),
+            Direction::Anticlockwise => self.last_ccw_millis = Some(current_time_millis),
+            Direction::None => {}
+        }
+        if dir != Direction::None {
+            self.last_step_millis = Some(current_time_millis);
+            self.last_step_direction = dir;
+            self.last_coast_millis = None;
+            self.settle_pending = true;
         }
 
+        self.direction = dir;
         dir
     }
+
+    /// Returns the current angular velocity, between 0.0 and 1.0
+    pub fn velocity(&self) -> Velocity {
+        Velocity::new(self.velocity)
+    }
+
+    /// Returns the timestamp of the latest Clockwise step, or `None` if there hasn't been one
+    pub fn last_cw_millis(&self) -> Option<u64> {
+        self.last_cw_millis
+    }
+
+    /// Returns the timestamp of the latest Anticlockwise step, or `None` if there hasn't been one
+    pub fn last_ccw_millis(&self) -> Option<u64> {
+        self.last_ccw_millis
+    }
+
+    /// Returns true exactly once when no step has occurred for `settle_ms` after the latest movement,
+    /// useful to commit a value once the user stops turning.
+    /// * `current_time_millis` - Current timestamp in ms, one before the latest step counts as no time passed
+    pub fn settle(&mut self, current_time_millis: u64) -> bool {
+        let last_step_millis = self.last_step_millis.unwrap_or(0);
+        if self.settle_pending
+            && current_time_millis.saturating_sub(last_step_millis) >= self.settle_ms as u64
+        {
+            self.settle_pending = false;
+            return true;
+        }
+        false
+    }
+
+    /// Like a flicked touch-scroll, keep emitting steps in the direction of the latest step once a fast spin stops.
+    /// While coasting is enabled and the velocity is above both the coast_velocity_min and the velocity floor, a step is emitted every `velocity_action_ms`
+    /// after the latest step, each one reducing the velocity by `velocity_inc_factor` until it falls below.
+    /// Call this periodically alongside `update`, it returns `Direction::None` between coast steps.
+    /// * `current_time_millis` - Current timestamp in ms, as for `update`
+    pub fn coast(&mut self, current_time_millis: u64) -> Direction {
+        if !self.coast_enabled
+            || self.last_step_direction == Direction::None
+            || self.velocity <= self.coast_velocity_min.max(self.velocity_floor)
+        {
+            return Direction::None;
+        }
+        let last_millis = self
+            .last_coast_millis
+            .or(self.last_step_millis)
+            .unwrap_or(0);
+        if current_time_millis.saturating_sub(last_millis) < self.velocity_action_ms {
+            return Direction::None;
+        }
+        self.last_coast_millis = Some(current_time_millis);
+        self.velocity -= self.velocity_inc_factor;
+        if self.velocity < self.velocity_floor {
+            self.velocity = self.velocity_floor;
+        }
+        self.last_step_direction
+    }
+
+    /// Returns the direction of the latest update only if the velocity is at least `min_velocity`,
+    /// otherwise `Direction::None`. This filters out slow, incidental nudges.
+    pub fn directed_above(&self, min_velocity: f32) -> Direction {
+        if self.velocity >= min_velocity {
+            self.direction
+        } else {
+            Direction::None
+        }
+    }
+
+    /// Reduce the angular velocity by the decreasing factor, stopping at the velocity floor
+    pub fn decay_velocity(&mut self) {
+        self.velocity -= self.velocity_dec_factor;
+        if self.velocity < self.velocity_floor {
+            self.velocity = self.velocity_floor;
+        }
+    }
+
+    /// Reset the angular velocity to the velocity floor, like `reset_on_detent` does,
+    /// and clear the timing state, leaving the configuration untouched
+    pub fn reset_velocity(&mut self) {
+        self.velocity = self.velocity_floor;
+        self.previous_time_millis = 0;
+        self.last_coast_millis = None;
+    }
+
+    /// Update and return the velocity-scaled increment, see `RotaryEncoder::update_accelerated`
+    pub fn update_accelerated(
+        &mut self,
+        dt_state: bool,
+        clk_state: bool,
+        current_time_millis: u64,
+    ) -> i32 {
+        let dir = self.update(dt_state, clk_state, current_time_millis);
+        let step = 1 + (self.accel_curve.apply(self.velocity) * (self.accel_max - 1) as f32) as i32;
+        match dir {
+            Direction::Clockwise => step,
+            Direction::Anticlockwise => -step,
+            Direction::None => 0,
+        }
+    }
 }
 
 impl Default for AngularVelocityMode {
@@ -139,6 +574,12 @@ impl Default for AngularVelocityMode {
     }
 }
 
+impl TimedMode for AngularVelocityMode {
+    fn update_at(&mut self, dt: bool, clk: bool, millis: u64) -> Direction {
+        self.update(dt, clk, millis)
+    }
+}
+
 impl<DT, CLK, MODE> RotaryEncoder<MODE, DT, CLK>
 where
     DT: InputPin,
@@ -146,10 +587,471 @@ where
 {
     /// Configure `RotaryEncoder` to use the AngularVelocityMode API
     pub fn into_angular_velocity_mode(self) -> RotaryEncoder<AngularVelocityMode, DT, CLK> {
-        RotaryEncoder {
-            pin_dt: self.pin_dt,
-            pin_clk: self.pin_clk,
-            mode: AngularVelocityMode::new(),
+        self.with_mode(AngularVelocityMode::new())
+    }
+
+    /// Configure `RotaryEncoder` to use the AngularVelocityMode API, starting at `velocity` clamped to 0.0..=1.0.
+    /// This continues acceleration across a brief interruption, such as resuming a spin.
+    pub fn into_angular_velocity_mode_primed(
+        self,
+        velocity: f32,
+    ) -> RotaryEncoder<AngularVelocityMode, DT, CLK> {
+        let mut mode = AngularVelocityMode::new();
+        mode.velocity = *Velocity::new(velocity);
+        self.with_mode(mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::digital::{Mock, Transaction};
+
+    /// Drives one clockwise detent whose step edge lands `interval` ms after the
+    /// preceding edge and returns the accelerated increment
+    fn cw_detent(mode: &mut AngularVelocityMode, time: &mut u64, interval: u64) -> i32 {
+        mode.update_accelerated(false, true, *time);
+        *time += interval;
+        let inc = mode.update_accelerated(false, false, *time);
+        *time += interval;
+        inc
+    }
+
+    #[test]
+    fn accelerated_increment_is_one_when_turning_slowly() {
+        let mut mode = AngularVelocityMode::new();
+        let mut time = 0;
+        for _ in 0..10 {
+            assert_eq!(cw_detent(&mut mode, &mut time, 100), 1);
+        }
+        assert_eq!(mode.velocity, 0.0);
+    }
+
+    #[test]
+    fn accelerated_increment_grows_when_spinning_fast() {
+        let mut mode = AngularVelocityMode::new();
+        let mut time = 0;
+        let first = cw_detent(&mut mode, &mut time, 5);
+        let mut last = first;
+        for _ in 0..10 {
+            last = cw_detent(&mut mode, &mut time, 5);
+        }
+        assert!(first < last);
+        assert_eq!(last, DEFAULT_ACCEL_MAX);
+    }
+
+    #[test]
+    fn accelerated_increment_is_negative_anticlockwise() {
+        let mut mode = AngularVelocityMode::new();
+        mode.update_accelerated(true, false, 0);
+        assert_eq!(mode.update_accelerated(false, false, 100), -1);
+        assert_eq!(mode.update_accelerated(false, false, 200), 0);
+    }
+
+    #[test]
+    fn raw_api_decodes_like_standard_mode() {
+        let mut raw_encoder = AngularVelocityMode::new();
+        let mut standard = crate::standard::StandardMode::new();
+        let seq = [
+            (true, true),
+            (false, true),
+            (false, false),
+            (true, false),
+            (false, false),
+        ];
+        for (time, &(dt, clk)) in seq.iter().enumerate() {
+            assert_eq!(
+                raw_encoder.update(dt, clk, 100 * time as u64),
+                standard.update(dt, clk)
+            );
+        }
+    }
+
+    #[test]
+    fn last_step_timestamps_are_tracked_per_direction() {
+        let mut mode = AngularVelocityMode::new();
+        assert_eq!(mode.last_cw_millis(), None);
+        assert_eq!(mode.last_ccw_millis(), None);
+
+        let mut time = 0;
+        cw_detent(&mut mode, &mut time, 100);
+        assert_eq!(mode.last_cw_millis(), Some(100));
+        assert_eq!(mode.last_ccw_millis(), None);
+
+        mode.update(true, false, 300);
+        assert_eq!(mode.update(false, false, 400), Direction::Anticlockwise);
+        assert_eq!(mode.last_cw_millis(), Some(100));
+        assert_eq!(mode.last_ccw_millis(), Some(400));
+    }
+
+    #[test]
+    fn settle_fires_once_after_quiet_window() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_settle_ms(200);
+        assert!(!mode.settle(1000));
+
+        let mut time = 0;
+        for _ in 0..3 {
+            cw_detent(&mut mode, &mut time, 10);
+        }
+        // The latest step landed at 50ms
+        assert!(!mode.settle(100));
+        assert!(!mode.settle(249));
+        assert!(mode.settle(250));
+        assert!(!mode.settle(300));
+        assert!(!mode.settle(10_000));
+
+        cw_detent(&mut mode, &mut time, 10);
+        assert!(mode.settle(time + 200));
+    }
+
+    #[test]
+    fn directed_above_gates_on_velocity() {
+        let mut mode = AngularVelocityMode::new();
+        let mut time = 0;
+        cw_detent(&mut mode, &mut time, 100);
+        assert_eq!(mode.directed_above(0.0), Direction::Clockwise);
+        assert_eq!(mode.directed_above(0.5), Direction::None);
+
+        for _ in 0..3 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+        assert_eq!(mode.directed_above(0.5), Direction::Clockwise);
+        assert_eq!(mode.directed_above(1.0), Direction::None);
+    }
+
+    #[test]
+    fn velocity_decays_to_the_floor() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_velocity_floor(0.3);
+        let mut time = 0;
+        for _ in 0..5 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+        assert_eq!(mode.velocity, 1.0);
+
+        for _ in 0..200 {
+            mode.decay_velocity();
+        }
+        assert_eq!(mode.velocity, 0.3);
+    }
+
+    #[test]
+    fn velocity_decays_to_zero_by_default() {
+        let mut mode = AngularVelocityMode::new();
+        mode.velocity = 0.5;
+        for _ in 0..100 {
+            mode.decay_velocity();
+        }
+        assert_eq!(mode.velocity, 0.0);
+    }
+
+    #[test]
+    fn reset_on_detent_drops_velocity_after_stopping() {
+        let mut plain = AngularVelocityMode::new();
+        let mut resetting = AngularVelocityMode::new();
+        resetting.reset_on_detent = true;
+
+        for mode in [&mut plain, &mut resetting] {
+            let mut time = 0;
+            for _ in 0..5 {
+                cw_detent(mode, &mut time, 5);
+            }
+            // Still turning, the next edge arrives inside the action window
+            mode.update(false, false, time + 10);
+            assert_eq!(mode.velocity, 1.0);
+            // Stopped on the detent
+            mode.update(false, false, time + 40);
+        }
+
+        assert_eq!(plain.velocity, 1.0);
+        assert_eq!(resetting.velocity, 0.0);
+    }
+
+    /// Returns the velocity after a single detent at `interval` on a fresh mode
+    fn velocity_after_detent(baseline_ms: u64, interval: u64) -> f32 {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_velocity_baseline_ms(baseline_ms);
+        cw_detent(&mut mode, &mut 0, interval);
+        mode.velocity
+    }
+
+    #[test]
+    fn action_window_increase_is_a_step_function() {
+        let velocities: Vec<f32> = [40, 30, 20, 10]
+            .iter()
+            .map(|&interval| velocity_after_detent(0, interval))
+            .collect();
+        assert_eq!(velocities, vec![0.0, 0.0, 0.2, 0.2]);
+    }
+
+    #[test]
+    fn baseline_increase_is_graduated() {
+        let velocities: Vec<f32> = [60, 40, 30, 20, 10]
+            .iter()
+            .map(|&interval| velocity_after_detent(50, interval))
+            .collect();
+        assert_eq!(velocities[0], 0.0);
+        for pair in velocities.windows(2) {
+            assert!(pair[0] < pair[1]);
+        }
+        assert!((velocities[4] - 0.16).abs() < 1e-6);
+    }
+
+    #[test]
+    fn velocity_is_clamped() {
+        assert_eq!(Velocity::new(1.5).as_f32(), 1.0);
+        assert_eq!(Velocity::new(-0.2).as_f32(), 0.0);
+        assert_eq!(Velocity::new(f32::NAN).as_f32(), 0.0);
+        assert_eq!(*Velocity::new(0.5), 0.5);
+        assert_eq!(f32::from(Velocity::new(0.25)), 0.25);
+    }
+
+    #[test]
+    fn exp_approximation_is_within_tolerance() {
+        for i in 0..=40 {
+            let x = i as f32 / 10.0;
+            let relative_error = (exp_polynomial(x) - x.exp()).abs() / x.exp();
+            assert!(
+                relative_error < 1e-4,
+                "exp({}) off by {}",
+                x,
+                relative_error
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "micromath")]
+    fn exp_approximation_matches_micromath() {
+        for i in 0..=40 {
+            let x = i as f32 / 10.0;
+            let expected = micromath::F32Ext::exp(x);
+            let relative_error = (exp_polynomial(x) - expected).abs() / expected;
+            assert!(
+                relative_error < EXP_TOLERANCE,
+                "exp({}) off by {}",
+                x,
+                relative_error
+            );
+        }
+    }
+
+    #[test]
+    fn exponential_curve_is_gentler_than_linear() {
+        let curve = AccelerationCurve::Exponential;
+        assert_eq!(curve.apply(0.0), 0.0);
+        assert!((curve.apply(1.0) - 1.0).abs() < 1e-4);
+        for &velocity in &[0.2, 0.5, 0.8] {
+            assert!(curve.apply(velocity) < AccelerationCurve::Linear.apply(velocity));
+        }
+    }
+
+    #[test]
+    fn exponential_curve_reaches_accel_max() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_accel_curve(AccelerationCurve::Exponential);
+        let mut time = 0;
+        let first = cw_detent(&mut mode, &mut time, 5);
+        let mut last = first;
+        for _ in 0..10 {
+            last = cw_detent(&mut mode, &mut time, 5);
+        }
+        assert_eq!(first, 1);
+        assert_eq!(last, DEFAULT_ACCEL_MAX);
+    }
+
+    #[test]
+    fn reset_velocity_clears_velocity_only() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_accel_max(4);
+        let mut time = 0;
+        for _ in 0..3 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+        assert!(mode.velocity > 0.0);
+
+        mode.reset_velocity();
+        assert_eq!(mode.velocity, 0.0);
+        assert_eq!(mode.previous_time_millis, 0);
+        assert_eq!(mode.accel_max, 4);
+        assert_eq!(mode.velocity_action_ms, DEFAULT_VELOCITY_ACTION_MS);
+
+        // Never below a configured floor
+        mode.set_velocity_floor(0.3);
+        cw_detent(&mut mode, &mut time, 5);
+        mode.reset_velocity();
+        assert_eq!(mode.velocity, 0.3);
+    }
+
+    #[test]
+    fn time_going_backwards_counts_as_zero_elapsed() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_reset_on_detent(true);
+        mode.update(false, true, 1_000);
+        mode.update(false, false, 1_000);
+        let velocity = *mode.velocity();
+
+        // The timer wrapped: now < previous must neither panic nor reset the velocity
+        mode.update(false, true, 10);
+        assert_eq!(mode.update(false, false, 5), Direction::Clockwise);
+        assert!(*mode.velocity() > velocity);
+        assert!(*mode.velocity() <= 1.0);
+    }
+
+    #[test]
+    fn latest_step_survives_a_timer_wrap() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_coast_enabled(true);
+        mode.set_reset_on_detent(true);
+        let mut time = 4_294_967_000;
+        for _ in 0..5 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+
+        // The 32-bit timer wraps, then a fast anticlockwise spin
+        let mut time = 0;
+        for _ in 0..5 {
+            mode.update(true, false, time + 5);
+            time += 10;
+            assert_eq!(mode.update(false, false, time), Direction::Anticlockwise);
+        }
+        assert_eq!(*mode.velocity(), 1.0);
+
+        assert!(!mode.settle(time + 499));
+        assert!(mode.settle(time + 500));
+        assert_eq!(mode.coast(time + 500), Direction::Anticlockwise);
+
+        // Resting on the detent long after the latest step drops the velocity
+        mode.update(false, false, time + 1_000);
+        assert_eq!(*mode.velocity(), 0.0);
+    }
+
+    #[test]
+    fn sensitivity_presets_set_the_velocity_parameters() {
+        let mut mode = AngularVelocityMode::new();
+        for (sensitivity, inc_factor, action_ms) in [
+            (Sensitivity::Low, 0.1, 15),
+            (Sensitivity::Medium, 0.2, 25),
+            (Sensitivity::High, 0.4, 40),
+        ] {
+            mode.apply_sensitivity(sensitivity);
+            assert_eq!(mode.velocity_inc_factor, inc_factor);
+            assert_eq!(mode.velocity_action_ms, action_ms);
+        }
+    }
+
+    #[test]
+    fn curved_velocity_applies_gamma() {
+        for &v in &[0.1, 0.25, 0.5, 0.9] {
+            let velocity = Velocity::new(v);
+            assert!((velocity.curved(1.0) - v).abs() < 1e-4);
+            assert!((velocity.curved(2.0) - v * v).abs() < 1e-4);
+            assert!(velocity.curved(2.0) < velocity.curved(1.0));
+            assert!(velocity.curved(0.5) > velocity.curved(1.0));
+        }
+        assert_eq!(Velocity::new(0.0).curved(2.0), 0.0);
+        assert!((Velocity::new(1.0).curved(2.0) - 1.0).abs() < 1e-4);
+        assert_eq!(Velocity::new(0.5).curved(0.0), 1.0);
+    }
+
+    #[test]
+    fn coast_emits_decaying_steps_after_a_fast_spin() {
+        let mut mode = AngularVelocityMode::new();
+        let mut time = 0;
+        for _ in 0..10 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+        assert_eq!(*mode.velocity(), 1.0);
+
+        // Disabled by default
+        assert_eq!(mode.coast(time + 100), Direction::None);
+
+        mode.set_coast_enabled(true);
+        // Not while still inside the action window of the latest step
+        assert_eq!(mode.coast(time), Direction::None);
+
+        let mut coasted = 0;
+        for _ in 0..100 {
+            time += 5;
+            match mode.coast(time) {
+                Direction::Clockwise => coasted += 1,
+                Direction::None => {}
+                Direction::Anticlockwise => panic!("coasted the wrong way"),
+            }
+        }
+        // 1.0, 0.8, 0.6 and 0.4 are above the coast minimum
+        assert_eq!(coasted, 4);
+        assert!(*mode.velocity() <= DEFAULT_COAST_VELOCITY_MIN);
+    }
+
+    #[test]
+    fn coast_stops_at_the_configured_minimum() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_coast_enabled(true);
+        mode.set_coast_velocity_min(0.7);
+        let mut time = 0;
+        for _ in 0..10 {
+            cw_detent(&mut mode, &mut time, 5);
+        }
+
+        let coasted = (1..100)
+            .filter(|i| mode.coast(time + i * 5) == Direction::Clockwise)
+            .count();
+        // Only 1.0 and 0.8 are above the configured minimum
+        assert_eq!(coasted, 2);
+    }
+
+    #[test]
+    fn coast_does_nothing_after_a_slow_turn() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_coast_enabled(true);
+        let mut time = 0;
+        for _ in 0..10 {
+            cw_detent(&mut mode, &mut time, 100);
+        }
+        assert_eq!(mode.coast(time + 100), Direction::None);
+    }
+
+    #[test]
+    fn cloned_modes_keep_independent_velocities() {
+        let mut mode = AngularVelocityMode::new();
+        let mut time = 0;
+        cw_detent(&mut mode, &mut time, 5);
+        let mut slow = mode;
+
+        let mut slow_time = time;
+        cw_detent(&mut mode, &mut time, 5);
+        cw_detent(&mut slow, &mut slow_time, 100);
+        assert!(mode.velocity() > slow.velocity());
+    }
+
+    #[test]
+    fn primed_mode_starts_at_the_given_velocity() {
+        let pins = || Mock::new(&[] as &[Transaction]);
+        let mut encoder = RotaryEncoder::new(pins(), pins()).into_angular_velocity_mode_primed(0.5);
+        assert_eq!(*encoder.velocity(), 0.5);
+        encoder.decay_velocity();
+        assert_eq!(*encoder.velocity(), 0.49);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+
+        let encoder = RotaryEncoder::new(pins(), pins()).into_angular_velocity_mode_primed(1.5);
+        assert_eq!(*encoder.velocity(), 1.0);
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn accel_max_is_clamped_to_one() {
+        let mut mode = AngularVelocityMode::new();
+        mode.set_accel_max(0);
+        let mut time = 0;
+        for _ in 0..10 {
+            assert_eq!(cw_detent(&mut mode, &mut time, 5), 1);
         }
     }
 }
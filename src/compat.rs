@@ -0,0 +1,2 @@
+/// embedded-hal 0.2 pins - suitable for projects yet to migrate to embedded-hal 1.0
+pub mod v02;
@@ -0,0 +1,118 @@
+use embedded_hal::digital::{ErrorKind, ErrorType, InputPin};
+use embedded_hal_0_2::digital::v2::InputPin as InputPinV02;
+
+use crate::{InitalizeMode, RotaryEncoder};
+
+/// V02Pin
+/// Adapts an embedded-hal 0.2 `digital::v2::InputPin` to the embedded-hal 1.0 `InputPin`,
+/// so every mode can decode it. Read errors are reported as `ErrorKind::Other`.
+#[derive(Clone, Copy, Debug)]
+pub struct V02Pin<P>(P);
+
+impl<P> V02Pin<P>
+where
+    P: InputPinV02,
+{
+    /// Wrap an embedded-hal 0.2 InputPin
+    pub fn new(pin: P) -> Self {
+        Self(pin)
+    }
+
+    /// Release the underlying embedded-hal 0.2 InputPin
+    pub fn into_inner(self) -> P {
+        self.0
+    }
+}
+
+impl<P> ErrorType for V02Pin<P>
+where
+    P: InputPinV02,
+{
+    type Error = ErrorKind;
+}
+
+impl<P> InputPin for V02Pin<P>
+where
+    P: InputPinV02,
+{
+    fn is_high(&mut self) -> Result<bool, ErrorKind> {
+        self.0.is_high().map_err(|_| ErrorKind::Other)
+    }
+
+    fn is_low(&mut self) -> Result<bool, ErrorKind> {
+        self.0.is_low().map_err(|_| ErrorKind::Other)
+    }
+}
+
+impl<DT, CLK> RotaryEncoder<InitalizeMode, V02Pin<DT>, V02Pin<CLK>>
+where
+    DT: InputPinV02,
+    CLK: InputPinV02,
+{
+    /// Initiates a new `RotaryEncoder` in `InitalizeMode`, taking two embedded-hal 0.2 InputPins
+    pub fn from_v02(pin_dt: DT, pin_clk: CLK) -> Self {
+        RotaryEncoder::new(V02Pin::new(pin_dt), V02Pin::new(pin_clk))
+    }
+}
+
+impl<MODE, DT, CLK> RotaryEncoder<MODE, V02Pin<DT>, V02Pin<CLK>>
+where
+    DT: InputPinV02,
+    CLK: InputPinV02,
+{
+    /// Release the underlying embedded-hal 0.2 InputPins back to the initiator
+    pub fn release_v02(self) -> (DT, CLK) {
+        let (pin_dt, pin_clk) = self.release();
+        (pin_dt.into_inner(), pin_clk.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Direction, ErrorPolicy, RotaryEncoder};
+    use embedded_hal_mock::eh0::digital::{Mock, State, Transaction};
+    use embedded_hal_mock::eh0::MockError;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn modes_decode_embedded_hal_0_2_pins() {
+        let dt = Mock::new(&vec![Transaction::get(State::Low); 4]);
+        let clk = Mock::new(&[
+            Transaction::get(State::High),
+            Transaction::get(State::Low),
+            Transaction::get(State::Low),
+            Transaction::get(State::Low),
+        ]);
+
+        let mut encoder = RotaryEncoder::from_v02(dt, clk).into_standard_mode();
+        encoder.update();
+        assert_eq!(encoder.update(), Direction::Clockwise);
+
+        // Switching modes keeps the 0.2 pins
+        let mut encoder = encoder.into_quadrature_table_mode(1);
+        assert_eq!(encoder.update(), Direction::None);
+        assert_eq!(encoder.update(), Direction::None);
+
+        let (mut dt, mut clk) = encoder.release_v02();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn read_errors_follow_the_error_policy() {
+        let dt = Mock::new(&[
+            Transaction::get(State::High),
+            Transaction::get(State::High).with_error(MockError::Io(ErrorKind::TimedOut)),
+        ]);
+        let clk = Mock::new(&vec![Transaction::get(State::Low); 2]);
+
+        let mut encoder = RotaryEncoder::from_v02(dt, clk).into_standard_mode();
+        encoder.set_error_policy(ErrorPolicy::HoldLast);
+        encoder.update();
+        assert_eq!(encoder.update(), Direction::None);
+
+        let (mut dt, mut clk) = encoder.release_v02();
+        dt.done();
+        clk.done();
+    }
+}
@@ -0,0 +1,168 @@
+use embedded_hal::digital::InputPin;
+
+use crate::table::{NoTrace, Step, StepMode, TableMode, Trace};
+use crate::{Direction, PollMode, RotaryEncoder};
+
+/// FullStepMode
+/// Decodes with `STATE_TABLE_FULL_STEPS`, yielding a single step once a full quadrature cycle returns to rest.
+/// This mode is suitable for encoders with a detent every full cycle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FullStepMode<T = NoTrace> {
+    /// The underlying full-step table decoder
+    table: TableMode<T>,
+}
+
+impl<DT, CLK, T> RotaryEncoder<FullStepMode<T>, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+    T: Trace,
+{
+    /// Updates the `RotaryEncoder`, updating the `direction` property
+    pub fn update(&mut self) -> Direction {
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder`, distinguishing still pins from movement part way through a step
+    pub fn update_step(&mut self) -> Step {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_step(dt, clk)
+    }
+}
+
+impl FullStepMode {
+    /// Initialises the FullStepMode
+    pub fn new() -> Self {
+        Self {
+            table: TableMode::new(StepMode::Full),
+        }
+    }
+}
+
+impl<T: Trace> FullStepMode<T> {
+    /// Set a hook called on every update with `(prev_state, new_state, direction)`, see `TableMode::with_trace`
+    pub fn with_trace<U: Trace>(self, trace: U) -> FullStepMode<U> {
+        FullStepMode {
+            table: self.table.with_trace(trace),
+        }
+    }
+
+    /// Remove the hook set by `with_trace`, keeping the decoding state
+    pub fn without_trace(self) -> FullStepMode {
+        self.with_trace(NoTrace)
+    }
+
+    /// Update to determine the direction
+    pub fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        self.table.update(dt, clk)
+    }
+
+    /// Update to determine the direction, see `TableMode::update_step`
+    pub fn update_step(&mut self, dt: bool, clk: bool) -> Step {
+        self.table.update_step(dt, clk)
+    }
+}
+
+impl Default for FullStepMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace> PollMode for FullStepMode<T> {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        FullStepMode::update(self, dt, clk)
+    }
+}
+
+impl<LOGIC, DT, CLK> RotaryEncoder<LOGIC, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Configure `RotaryEncoder` to use the full-step mode
+    pub fn into_fullstep_mode(self) -> RotaryEncoder<FullStepMode, DT, CLK> {
+        self.with_mode(FullStepMode::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drive_sequence;
+    use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+
+    #[test]
+    fn clockwise_steps_once_per_cycle() {
+        let mut mode = FullStepMode::new();
+        // 11→01→00→10→11
+        let seq = [(false, true), (false, false), (true, false), (true, true)];
+        assert_eq!(
+            drive_sequence(&mut mode, &seq),
+            vec![
+                Direction::None,
+                Direction::None,
+                Direction::None,
+                Direction::Clockwise,
+            ]
+        );
+    }
+
+    #[test]
+    fn anticlockwise_steps_once_per_cycle() {
+        let mut mode = FullStepMode::new();
+        // 11→10→00→01→11
+        let seq = [(true, false), (false, false), (false, true), (true, true)];
+        assert_eq!(
+            drive_sequence(&mut mode, &seq),
+            vec![
+                Direction::None,
+                Direction::None,
+                Direction::None,
+                Direction::Anticlockwise,
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_reports_every_transition() {
+        let mut traced = Vec::new();
+        let mut mode =
+            FullStepMode::new().with_trace(|prev, next, dir| traced.push((prev, next, dir)));
+        // 11→01→00→10→11
+        drive_sequence(
+            &mut mode,
+            &[(false, true), (false, false), (true, false), (true, true)],
+        );
+        let mut mode = mode.without_trace();
+        // R_START → R_CW_BEGIN → R_CW_NEXT → R_CW_FINAL → R_START
+        assert_eq!(
+            traced,
+            vec![
+                (0, 2, Direction::None),
+                (2, 3, Direction::None),
+                (3, 1, Direction::None),
+                (1, 0, Direction::Clockwise),
+            ]
+        );
+
+        assert_eq!(mode.update(false, true), Direction::None);
+    }
+
+    #[test]
+    fn wrapper_update_reads_the_pins() {
+        let get = |level| Transaction::get(if level { State::High } else { State::Low });
+        let seq = [(false, true), (false, false), (true, false), (true, true)];
+        let dt = Mock::new(&seq.iter().map(|&(dt, _)| get(dt)).collect::<Vec<_>>());
+        let clk = Mock::new(&seq.iter().map(|&(_, clk)| get(clk)).collect::<Vec<_>>());
+
+        let mut encoder = RotaryEncoder::new(dt, clk).into_fullstep_mode();
+        let dirs: Vec<Direction> = (0..seq.len()).map(|_| encoder.update()).collect();
+        assert_eq!(dirs.last(), Some(&Direction::Clockwise));
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+}
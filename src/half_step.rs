@@ -0,0 +1,134 @@
+use embedded_hal::digital::InputPin;
+
+use crate::table::{NoTrace, Step, StepMode, TableMode, Trace};
+use crate::{Direction, PollMode, RotaryEncoder};
+
+/// HalfStepMode
+/// Decodes with `STATE_TABLE_HALF_STEPS`, yielding a step at both the 00 and 11 rest states.
+/// This mode is suitable for indentless encoders or encoders with a detent every half cycle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct HalfStepMode<T = NoTrace> {
+    /// The underlying half-step table decoder
+    table: TableMode<T>,
+}
+
+impl<DT, CLK, T> RotaryEncoder<HalfStepMode<T>, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+    T: Trace,
+{
+    /// Updates the `RotaryEncoder`, updating the `direction` property
+    pub fn update(&mut self) -> Direction {
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder`, distinguishing still pins from movement part way through a step
+    pub fn update_step(&mut self) -> Step {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_step(dt, clk)
+    }
+}
+
+impl HalfStepMode {
+    /// Initialises the HalfStepMode
+    pub fn new() -> Self {
+        Self {
+            table: TableMode::new(StepMode::Half),
+        }
+    }
+}
+
+impl<T: Trace> HalfStepMode<T> {
+    /// Set a hook called on every update with `(prev_state, new_state, direction)`, see `TableMode::with_trace`
+    pub fn with_trace<U: Trace>(self, trace: U) -> HalfStepMode<U> {
+        HalfStepMode {
+            table: self.table.with_trace(trace),
+        }
+    }
+
+    /// Remove the hook set by `with_trace`, keeping the decoding state
+    pub fn without_trace(self) -> HalfStepMode {
+        self.with_trace(NoTrace)
+    }
+
+    /// Update to determine the direction
+    pub fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        self.table.update(dt, clk)
+    }
+
+    /// Update to determine the direction, see `TableMode::update_step`
+    pub fn update_step(&mut self, dt: bool, clk: bool) -> Step {
+        self.table.update_step(dt, clk)
+    }
+}
+
+impl Default for HalfStepMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Trace> PollMode for HalfStepMode<T> {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        HalfStepMode::update(self, dt, clk)
+    }
+}
+
+impl<LOGIC, DT, CLK> RotaryEncoder<LOGIC, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Configure `RotaryEncoder` to use the half-step mode
+    pub fn into_halfstep_mode(self) -> RotaryEncoder<HalfStepMode, DT, CLK> {
+        self.with_mode(HalfStepMode::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drive_sequence;
+
+    #[test]
+    fn clockwise_steps_at_each_half_cycle() {
+        let mut mode = HalfStepMode::new();
+        // 11→01→00→10→11
+        let seq = [(false, true), (false, false), (true, false), (true, true)];
+        assert_eq!(
+            drive_sequence(&mut mode, &seq),
+            vec![
+                Direction::None,
+                Direction::Clockwise,
+                Direction::None,
+                Direction::Clockwise,
+            ]
+        );
+    }
+
+    #[test]
+    fn anticlockwise_steps_at_each_half_cycle() {
+        let mut mode = HalfStepMode::new();
+        // 11→10→00→01→11
+        let seq = [(true, false), (false, false), (false, true), (true, true)];
+        assert_eq!(
+            drive_sequence(&mut mode, &seq),
+            vec![
+                Direction::None,
+                Direction::Anticlockwise,
+                Direction::None,
+                Direction::Anticlockwise,
+            ]
+        );
+    }
+
+    #[test]
+    fn bounce_back_yields_nothing() {
+        let mut mode = HalfStepMode::new();
+        // 11→01→11 never reaches the next rest state
+        let seq = [(false, true), (true, true), (false, true), (true, true)];
+        assert_eq!(drive_sequence(&mut mode, &seq), vec![Direction::None; 4]);
+    }
+}
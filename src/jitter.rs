@@ -0,0 +1,137 @@
+use crate::Direction;
+
+/// JitterMeter
+/// A diagnostic counting how many reported steps immediately reverse the previous one, a proxy for jitter.
+/// Feed it every `Direction` a mode returns. A high proportion of reversals means the threshold or
+/// debounce is too low for the encoder, a clean spin produces none.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct JitterMeter {
+    /// The number of steps after which the counts are halved
+    window: u32,
+    /// The number of steps recorded
+    steps: u32,
+    /// The number of steps reversing the previous one
+    reversals: u32,
+    /// The latest step
+    last: Direction,
+}
+
+impl JitterMeter {
+    /// Initialises the JitterMeter. Once `window` steps are recorded both counts are halved,
+    /// so older steps weigh less and the meter follows changes in behaviour. A `window` below 2 is raised to 2
+    pub fn new(window: u32) -> Self {
+        Self {
+            window: window.max(2),
+            steps: 0,
+            reversals: 0,
+            last: Direction::None,
+        }
+    }
+
+    /// Record the result of an update, `Direction::None` is ignored
+    pub fn record(&mut self, dir: Direction) {
+        if dir == Direction::None {
+            return;
+        }
+        if self.last == dir.opposite() {
+            self.reversals += 1;
+        }
+        self.steps += 1;
+        self.last = dir;
+        if self.steps >= self.window {
+            self.steps /= 2;
+            self.reversals /= 2;
+        }
+    }
+
+    /// Returns the number of steps counted
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    /// Returns the number of counted steps which reversed the previous step
+    pub fn reversals(&self) -> u32 {
+        self.reversals
+    }
+
+    /// Returns reversals / steps, between 0.0 and 1.0, or 0.0 when no steps are counted
+    #[cfg(feature = "float")]
+    pub fn jitter_ratio(&self) -> f32 {
+        if self.steps == 0 {
+            0.0
+        } else {
+            self.reversals as f32 / self.steps as f32
+        }
+    }
+
+    /// Clear the counts
+    pub fn reset(&mut self) {
+        *self = Self::new(self.window);
+    }
+}
+
+impl Default for JitterMeter {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records every direction of `dirs`
+    fn meter(dirs: &[Direction]) -> JitterMeter {
+        let mut meter = JitterMeter::default();
+        for &dir in dirs {
+            meter.record(dir);
+        }
+        meter
+    }
+
+    #[test]
+    fn clean_spin_has_no_reversals() {
+        let meter = meter(&[
+            Direction::Clockwise,
+            Direction::None,
+            Direction::Clockwise,
+            Direction::Clockwise,
+            Direction::None,
+            Direction::Clockwise,
+        ]);
+        assert_eq!((meter.steps(), meter.reversals()), (4, 0));
+        #[cfg(feature = "float")]
+        assert_eq!(meter.jitter_ratio(), 0.0);
+    }
+
+    #[test]
+    fn jittery_sequence_counts_reversals() {
+        let meter = meter(&[
+            Direction::Clockwise,
+            Direction::Anticlockwise,
+            Direction::None,
+            Direction::Clockwise,
+            Direction::Anticlockwise,
+        ]);
+        assert_eq!((meter.steps(), meter.reversals()), (4, 3));
+        #[cfg(feature = "float")]
+        assert_eq!(meter.jitter_ratio(), 0.75);
+    }
+
+    #[test]
+    fn counts_halve_once_the_window_fills() {
+        let mut meter = JitterMeter::new(4);
+        for dir in [Direction::Clockwise, Direction::Anticlockwise]
+            .iter()
+            .cycle()
+            .take(4)
+        {
+            meter.record(*dir);
+        }
+        // 4 steps with 3 reversals halve to 2 and 1
+        assert_eq!((meter.steps(), meter.reversals()), (2, 1));
+
+        meter.reset();
+        assert_eq!((meter.steps(), meter.reversals()), (0, 0));
+    }
+}
@@ -1,18 +1,59 @@
 //! # rotary-encoder
 //! A rotary encoder library built for embedded applications
+//!
+//! ## Features
+//! - `half-step` - adds `HalfStepMode`
+//! - `std` - adds `StdEncoder`, which reads timestamps from `std::time::Instant`
+//! - `float` (default) - adds every mode using `f32` (`AngularVelocityMode`, `StandardVelocityMode` and `StdEncoder`).
+//!   Disable default features for builds without floating point, leaving `StandardMode`, `QuadratureTableMode`,
+//!   `FullStepMode` and `TableMode`
+//! - `micromath` - computes the `Exponential` acceleration curve with `micromath` rather than a polynomial approximation
+//! - `eh02` - adds `compat::v02`, adapting embedded-hal 0.2 pins to every mode
+//! - `test-util` - adds the `replay` module for decoding captured logic-analyzer traces
 
 #![deny(missing_docs)]
 #![deny(warnings)]
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+#![cfg_attr(feature = "float", allow(clippy::disallowed_types))]
+#![cfg_attr(
+    not(feature = "float"),
+    deny(clippy::disallowed_types, clippy::float_arithmetic)
+)]
+
+use core::ops::{AddAssign, SubAssign};
 
 use embedded_hal::digital::InputPin;
 
 /// Angular velocity api
+#[cfg(feature = "float")]
 pub mod angular_velocity;
+/// Compatibility with earlier embedded-hal versions
+#[cfg(feature = "eh02")]
+pub mod compat;
+/// Full-step encoder - suitable for indented encoders with a detent every full cycle
+pub mod full_step;
+/// Half-step encoder - suitable for intentless encoders
+#[cfg(feature = "half-step")]
+pub mod half_step;
+/// Jitter diagnostics - suitable for tuning thresholds in the field
+pub mod jitter;
+/// Port backed pins - suitable for GPIO expanders exposing both pins in one register
+pub mod port;
 /// Quadrature table encoder - suitable for intentless encoders
 pub mod quadrature;
+/// Trace replay - decodes captured `dt,clk` samples to reproduce misbehaving encoders
+#[cfg(feature = "test-util")]
+pub mod replay;
 /// Standard encoder - suitable for indented encoders
 pub mod standard;
+/// Standard encoder with a poll-count velocity estimate - suitable when no clock is available
+#[cfg(feature = "float")]
+pub mod standard_velocity;
+/// `std::time::Instant` based encoder - suitable for host-side prototyping
+#[cfg(feature = "std")]
+pub mod std_encoder;
+/// State table encoder - switchable between full and half step decoding
+pub mod table;
 
 /// Direction of Rotary Encoder rotation
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -25,11 +66,156 @@ pub enum Direction {
     Anticlockwise,
 }
 
+impl Direction {
+    /// Returns the opposite direction, useful for correcting an encoder mounted the other way around.
+    /// `None` remains `None`
+    pub const fn opposite(self) -> Direction {
+        match self {
+            Direction::None => Direction::None,
+            Direction::Clockwise => Direction::Anticlockwise,
+            Direction::Anticlockwise => Direction::Clockwise,
+        }
+    }
+
+    /// Apply the direction to `value`, adding `step` for Clockwise, subtracting it for Anticlockwise
+    /// and leaving `value` untouched for `None`
+    pub fn apply<T: AddAssign + SubAssign>(self, value: &mut T, step: T) {
+        match self {
+            Direction::None => {}
+            Direction::Clockwise => *value += step,
+            Direction::Anticlockwise => *value -= step,
+        }
+    }
+}
+
+impl core::fmt::Display for Direction {
+    /// Formats the direction compactly for logging: "CW", "CCW" or "-" for `None`
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(match self {
+            Direction::None => "-",
+            Direction::Clockwise => "CW",
+            Direction::Anticlockwise => "CCW",
+        })
+    }
+}
+
+/// One of the two encoder pins, identifying which changed on an edge interrupt
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Pin {
+    /// The DT pin
+    Dt,
+    /// The CLK pin
+    Clk,
+}
+
+/// A mode which decodes from the pin levels alone, suitable for polling
+pub trait PollMode {
+    /// Update to determine the direction
+    fn update(&mut self, dt: bool, clk: bool) -> Direction;
+}
+
+/// Collects the direction of each update over `seq`, shared by the tests of every `PollMode`
+#[cfg(test)]
+pub(crate) fn drive_sequence<MODE: PollMode>(
+    mode: &mut MODE,
+    seq: &[(bool, bool)],
+) -> Vec<Direction> {
+    seq.iter().map(|&(dt, clk)| mode.update(dt, clk)).collect()
+}
+
+/// A mode which needs the current time in milliseconds to decode
+pub trait TimedMode {
+    /// Update to determine the direction at the timestamp `millis`
+    fn update_at(&mut self, dt: bool, clk: bool, millis: u64) -> Direction;
+}
+
+/// A source of the current time in milliseconds, implemented once for the application's timer.
+/// Closures returning the time are clocks too.
+pub trait Clock {
+    /// Returns the current time in milliseconds
+    fn now_millis(&self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: Fn() -> u64,
+{
+    fn now_millis(&self) -> u64 {
+        self()
+    }
+}
+
+/// Clocked
+/// Pairs a `TimedMode` with a `Clock`, turning it into a `PollMode`
+pub struct Clocked<MODE, CLOCK> {
+    mode: MODE,
+    clock: CLOCK,
+}
+
+impl<MODE, CLOCK> Clocked<MODE, CLOCK>
+where
+    MODE: TimedMode,
+    CLOCK: Clock,
+{
+    /// Initiates a new `Clocked` mode, `clock` is read on every update
+    pub fn new(mode: MODE, clock: CLOCK) -> Self {
+        Self { mode, clock }
+    }
+
+    /// Borrow the underlying mode
+    pub fn mode(&mut self) -> &mut MODE {
+        &mut self.mode
+    }
+
+    /// Release the underlying mode and clock
+    pub fn release(self) -> (MODE, CLOCK) {
+        (self.mode, self.clock)
+    }
+}
+
+impl<MODE, CLOCK> PollMode for Clocked<MODE, CLOCK>
+where
+    MODE: TimedMode,
+    CLOCK: Clock,
+{
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        let millis = self.clock.now_millis();
+        self.mode.update_at(dt, clk, millis)
+    }
+}
+
+/// What the `RotaryEncoder` uses as the level of an InputPin which fails to read
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ErrorPolicy {
+    /// Treat the pin as low, the default
+    #[default]
+    DefaultLow,
+    /// Hold the last successfully read level, preventing phantom edges on transiently failing pins such as I/O expanders
+    HoldLast,
+}
+
+/// Presets for quick configuration of a mode's sensitivity, see `apply_sensitivity` on the supporting modes.
+/// The fine-grained setters remain available to adjust a preset afterwards.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Sensitivity {
+    /// Steps need more movement, velocity builds slowly.
+    /// `QuadratureTableMode` threshold 4, `AngularVelocityMode` inc factor 0.1 within a 15ms action window
+    Low,
+    /// A balance between the two.
+    /// `QuadratureTableMode` threshold 2, `AngularVelocityMode` inc factor 0.2 within a 25ms action window
+    Medium,
+    /// Every transition counts, velocity builds quickly.
+    /// `QuadratureTableMode` threshold 1, `AngularVelocityMode` inc factor 0.4 within a 40ms action window
+    High,
+}
+
 /// Rotary Encoder
 pub struct RotaryEncoder<MODE, DT, CLK> {
     mode: MODE,
     pin_dt: DT,
     pin_clk: CLK,
+    error_policy: ErrorPolicy,
+    last_levels: (bool, bool),
 }
 
 /// Common
@@ -52,10 +238,99 @@ where
     pub fn mode(&mut self) -> &mut MODE {
         &mut self.mode
     }
+
+    /// Drop the mode state and return to `InitalizeMode`, keeping the InputPins so a different mode can be configured
+    pub fn into_initialize_mode(self) -> RotaryEncoder<InitalizeMode, DT, CLK> {
+        self.with_mode(InitalizeMode {})
+    }
+
+    /// Set the policy applied when reading an InputPin fails, `ErrorPolicy::DefaultLow` by default
+    pub fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Configure `RotaryEncoder` to use a pre-built mode instance, keeping the InputPins and configuration.
+    /// This is useful for modes with many parameters or custom constructors
+    pub fn with_mode<NEW>(self, mode: NEW) -> RotaryEncoder<NEW, DT, CLK> {
+        RotaryEncoder {
+            mode,
+            pin_dt: self.pin_dt,
+            pin_clk: self.pin_clk,
+            error_policy: self.error_policy,
+            last_levels: self.last_levels,
+        }
+    }
+
+    /// Read the DT and CLK levels without decoding them, applying the `ErrorPolicy` to failed reads.
+    /// The mode state is untouched, which is useful for verifying wiring or seeding a mode such as
+    /// `QuadratureTableMode::from_state`.
+    pub fn read_levels(&mut self) -> (bool, bool) {
+        let (last_dt, last_clk) = match self.error_policy {
+            ErrorPolicy::DefaultLow => (false, false),
+            ErrorPolicy::HoldLast => self.last_levels,
+        };
+        self.last_levels = (
+            self.pin_dt.is_high().unwrap_or(last_dt),
+            self.pin_clk.is_high().unwrap_or(last_clk),
+        );
+        self.last_levels
+    }
+}
+
+impl<MODE, DT, CLK> RotaryEncoder<MODE, DT, CLK>
+where
+    MODE: TimedMode,
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Updates the `RotaryEncoder` at the current time read from `clock`, instead of passing the milliseconds in
+    pub fn poll<C: Clock>(&mut self, clock: &C) -> Direction {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_at(dt, clk, clock.now_millis())
+    }
+}
+
+impl<MODE, PIN> RotaryEncoder<MODE, PIN, PIN>
+where
+    PIN: InputPin,
+{
+    /// Swap the roles of the DT and CLK pins, for encoders discovered to be wired the other way around.
+    /// Unlike flipping the returned `Direction`, the mode decodes the swapped levels from then on.
+    /// Only available when both pins are the same type.
+    pub fn swap_pins(&mut self) {
+        core::mem::swap(&mut self.pin_dt, &mut self.pin_clk);
+        self.last_levels = (self.last_levels.1, self.last_levels.0);
+    }
 }
 
 /// InitializeMode
 /// This is the plain `RotaryEncoder` with no business logic attached. In order to use the `RotaryEncoder` it must be initialized to a valid `Mode`
+///
+/// `update` only exists once a mode is configured:
+/// ```
+/// # use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+/// # use rotary_encoder_embedded::RotaryEncoder;
+/// # let dt = Mock::new(&[Transaction::get(State::High)]);
+/// # let clk = Mock::new(&[Transaction::get(State::High)]);
+/// let mut encoder = RotaryEncoder::new(dt, clk).into_standard_mode();
+/// encoder.update();
+/// # let (mut dt, mut clk) = encoder.release();
+/// # dt.done();
+/// # clk.done();
+/// ```
+/// Calling it in `InitalizeMode` fails to compile:
+/// ```compile_fail
+/// # use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+/// # use rotary_encoder_embedded::RotaryEncoder;
+/// # let dt = Mock::new(&[Transaction::get(State::High)]);
+/// # let clk = Mock::new(&[Transaction::get(State::High)]);
+/// let mut encoder = RotaryEncoder::new(dt, clk);
+/// encoder.update();
+/// # let (mut dt, mut clk) = encoder.release();
+/// # dt.done();
+/// # clk.done();
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct InitalizeMode;
 
 impl<DT, CLK> RotaryEncoder<InitalizeMode, DT, CLK>
@@ -69,17 +344,190 @@ where
             pin_dt,
             pin_clk,
             mode: InitalizeMode {},
+            error_policy: ErrorPolicy::default(),
+            last_levels: (false, false),
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    #[cfg(feature = "float")]
     use crate::{
-        angular_velocity::AngularVelocityMode, quadrature::QuadratureTableMode,
-        standard::StandardMode, Direction, RotaryEncoder,
+        angular_velocity::AngularVelocityMode, standard_velocity::StandardVelocityMode, Clock,
+        Clocked, TimedMode,
     };
+    use crate::{
+        quadrature::QuadratureTableMode, standard::StandardMode, table::TableMode, Direction,
+        ErrorPolicy, PollMode, RotaryEncoder,
+    };
+    #[cfg(feature = "float")]
+    use core::cell::Cell;
     use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+    use embedded_hal_mock::eh1::MockError;
+    use std::io::ErrorKind;
+
+    #[test]
+    fn direction_opposite() {
+        assert_eq!(Direction::None.opposite(), Direction::None);
+        assert_eq!(Direction::Clockwise.opposite(), Direction::Anticlockwise);
+        assert_eq!(Direction::Anticlockwise.opposite(), Direction::Clockwise);
+    }
+
+    #[test]
+    fn direction_apply() {
+        let mut value = 10;
+        Direction::Clockwise.apply(&mut value, 5);
+        assert_eq!(value, 15);
+        Direction::Anticlockwise.apply(&mut value, 20);
+        assert_eq!(value, -5);
+        Direction::None.apply(&mut value, 100);
+        assert_eq!(value, -5);
+    }
+
+    #[test]
+    fn direction_display() {
+        assert_eq!(Direction::None.to_string(), "-");
+        assert_eq!(Direction::Clockwise.to_string(), "CW");
+        assert_eq!(format!("{:>4}", Direction::Anticlockwise), " CCW");
+    }
+
+    #[test]
+    fn modes_implement_default() {
+        fn default_mode<MODE: Default>() -> MODE {
+            MODE::default()
+        }
+
+        assert_eq!(
+            default_mode::<StandardMode>().update(true, false),
+            StandardMode::new().update(true, false)
+        );
+        assert_eq!(
+            default_mode::<QuadratureTableMode>().update(true, false),
+            Direction::Clockwise
+        );
+        assert_eq!(
+            default_mode::<TableMode>(),
+            TableMode::new(crate::table::StepMode::Full)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn float_modes_implement_default() {
+        fn default_mode<MODE: Default>() -> MODE {
+            MODE::default()
+        }
+
+        assert_eq!(
+            default_mode::<AngularVelocityMode>().update(true, false, 0),
+            AngularVelocityMode::new().update(true, false, 0)
+        );
+        assert_eq!(
+            default_mode::<StandardVelocityMode>(),
+            StandardVelocityMode::new()
+        );
+    }
+
+    /// Drives a clockwise step through the `PollMode` trait
+    fn drive<MODE: PollMode>(mode: &mut MODE) -> Direction {
+        mode.update(false, true);
+        mode.update(false, false)
+    }
+
+    #[test]
+    fn poll_mode_trait() {
+        assert_eq!(drive(&mut StandardMode::new()), Direction::Clockwise);
+        assert_eq!(
+            drive(&mut QuadratureTableMode::new(1)),
+            Direction::Clockwise
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn float_poll_mode_trait() {
+        assert_eq!(
+            drive(&mut StandardVelocityMode::new()),
+            Direction::Clockwise
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn timed_mode_trait() {
+        let mut timed = AngularVelocityMode::new();
+        timed.update_at(false, true, 0);
+        assert_eq!(timed.update_at(false, false, 5), Direction::Clockwise);
+
+        // The same `Clock` drives both `Clocked` and `RotaryEncoder::poll`
+        let clock = SteppingClock {
+            now: Cell::new(0),
+            step: 5,
+        };
+        let mut clocked = Clocked::new(AngularVelocityMode::new(), clock);
+        assert_eq!(drive(&mut clocked), Direction::Clockwise);
+        assert_eq!(*clocked.mode().velocity(), 0.2);
+
+        // Closures are clocks too
+        let now = Cell::new(0);
+        let mut clocked = Clocked::new(AngularVelocityMode::new(), || {
+            now.set(now.get() + 5);
+            now.get()
+        });
+        assert_eq!(drive(&mut clocked), Direction::Clockwise);
+        assert_eq!(*clocked.mode().velocity(), 0.2);
+    }
+
+    /// A clock advancing `step` milliseconds on every read
+    #[cfg(feature = "float")]
+    struct SteppingClock {
+        now: Cell<u64>,
+        step: u64,
+    }
+
+    #[cfg(feature = "float")]
+    impl Clock for SteppingClock {
+        fn now_millis(&self) -> u64 {
+            self.now.set(self.now.get() + self.step);
+            self.now.get()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "float")]
+    fn poll_reads_the_time_from_the_clock() {
+        // Four clockwise detents
+        let dt = || Mock::new(&vec![Transaction::get(State::Low); 8]);
+        let clk = || {
+            Mock::new(
+                &[Transaction::get(State::High), Transaction::get(State::Low)]
+                    .iter()
+                    .cycle()
+                    .take(8)
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let clock = SteppingClock {
+            now: Cell::new(0),
+            step: 5,
+        };
+        let mut polled = RotaryEncoder::new(dt(), clk()).into_angular_velocity_mode();
+        let mut direct = RotaryEncoder::new(dt(), clk()).into_angular_velocity_mode();
+        for i in 1..=8 {
+            assert_eq!(polled.poll(&clock), direct.update(i * 5));
+            assert_eq!(polled.velocity(), direct.velocity());
+        }
+        assert!(*polled.velocity() > 0.0);
+
+        for encoder in [polled, direct] {
+            let (mut dt, mut clk) = encoder.release();
+            dt.done();
+            clk.done();
+        }
+    }
 
     #[test]
     fn standard_mode() {
@@ -101,6 +549,116 @@ mod test {
         clk.done();
     }
 
+    #[test]
+    fn swap_pins_flips_decode_direction() {
+        let a = Mock::new(&vec![Transaction::get(State::Low); 4]);
+        let b = Mock::new(&[
+            Transaction::get(State::High),
+            Transaction::get(State::Low),
+            Transaction::get(State::High),
+            Transaction::get(State::Low),
+        ]);
+
+        let mut encoder = RotaryEncoder::new(a, b).into_standard_mode();
+        encoder.update();
+        assert_eq!(encoder.update(), Direction::Clockwise);
+
+        encoder.swap_pins();
+        encoder.update();
+        assert_eq!(encoder.update(), Direction::Anticlockwise);
+
+        let (mut b, mut a) = encoder.release();
+        a.done();
+        b.done();
+    }
+
+    #[test]
+    fn into_initialize_mode_keeps_pins() {
+        let dt = Mock::new(&[Transaction::get(State::High), Transaction::get(State::High)]);
+        let clk = Mock::new(&[Transaction::get(State::Low), Transaction::get(State::Low)]);
+
+        let mut encoder = RotaryEncoder::new(dt, clk).into_standard_mode();
+        encoder.update();
+
+        let mut encoder = encoder.into_initialize_mode().into_quadrature_table_mode(1);
+        // 00 -> 01 = CW
+        assert_eq!(encoder.update(), Direction::Clockwise);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    /// A pin reading high with a failed read in the middle
+    fn flaky_high_pin() -> Mock {
+        Mock::new(&[
+            Transaction::get(State::High),
+            Transaction::get(State::High).with_error(MockError::Io(ErrorKind::TimedOut)),
+            Transaction::get(State::High),
+        ])
+    }
+
+    #[test]
+    fn default_low_error_policy_reads_failures_as_low() {
+        let clk = Mock::new(&vec![Transaction::get(State::Low); 3]);
+        let mut encoder = RotaryEncoder::new(flaky_high_pin(), clk).into_standard_mode();
+        encoder.update();
+        // The failed read looks like a falling DT edge
+        assert_eq!(encoder.update(), Direction::Anticlockwise);
+        encoder.update();
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn hold_last_error_policy_prevents_phantom_edges() {
+        let clk = Mock::new(&vec![Transaction::get(State::Low); 3]);
+        let mut encoder = RotaryEncoder::new(flaky_high_pin(), clk).into_standard_mode();
+        encoder.set_error_policy(ErrorPolicy::HoldLast);
+        for _ in 0..3 {
+            assert_eq!(encoder.update(), Direction::None);
+        }
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn read_levels_leaves_the_mode_untouched() {
+        let dt = Mock::new(&[Transaction::get(State::High), Transaction::get(State::Low)]);
+        let clk = Mock::new(&[Transaction::get(State::Low), Transaction::get(State::Low)]);
+
+        let mut encoder = RotaryEncoder::new(dt, clk).into_quadrature_table_mode(1);
+        assert_eq!(encoder.read_levels(), (true, false));
+        assert!(!encoder.mode().changed());
+        // The mode never saw 01, so 00 -> 00 is no movement
+        assert_eq!(encoder.update(), Direction::None);
+        assert!(!encoder.mode().changed());
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn with_mode_installs_a_pre_built_mode() {
+        let dt = Mock::new(&[Transaction::get(State::Low), Transaction::get(State::Low)]);
+        let clk = Mock::new(&[Transaction::get(State::High), Transaction::get(State::Low)]);
+
+        let mode = QuadratureTableMode::from_state(0b10, 1);
+        let mut encoder = RotaryEncoder::new(dt, clk).with_mode(mode);
+        // Seeded at 10 so there's no phantom first step
+        assert_eq!(encoder.update(), Direction::None);
+        assert_eq!(encoder.update(), Direction::Clockwise);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
     #[test]
     fn quadrature_table_mode() {
         let expectations = [Transaction::get(State::High)];
@@ -122,6 +680,7 @@ mod test {
     }
 
     #[test]
+    #[cfg(feature = "float")]
     fn angular_velocity_mode() {
         let expectations = [Transaction::get(State::High)];
 
@@ -143,3 +702,43 @@ mod test {
         clk.done();
     }
 }
+
+/// Compiled only without `float`, exercising every mode that remains available.
+/// Any `f32` or `f64` type or float arithmetic left in this build fails `cargo clippy`, through the
+/// `disallowed-types` in `clippy.toml`. `cargo build` and `cargo test` do not check it, CI runs clippy without `float`.
+#[cfg(all(test, not(feature = "float")))]
+mod no_float {
+    use crate::{
+        drive_sequence,
+        quadrature::QuadratureTableMode,
+        standard::StandardMode,
+        table::{StepMode, TableMode},
+        Direction, PollMode,
+    };
+
+    /// Drives a full clockwise cycle from rest at 11 and returns the last direction decoded
+    fn cycle<MODE: PollMode>(mut mode: MODE) -> Direction {
+        drive_sequence(
+            &mut mode,
+            &[
+                (true, true),
+                (false, true),
+                (false, false),
+                (true, false),
+                (true, true),
+            ],
+        )
+        .into_iter()
+        .rev()
+        .find(|&dir| dir != Direction::None)
+        .unwrap_or(Direction::None)
+    }
+
+    #[test]
+    fn integer_modes_are_available() {
+        assert_eq!(cycle(StandardMode::new()), Direction::Clockwise);
+        assert_eq!(cycle(QuadratureTableMode::new(4)), Direction::Clockwise);
+        assert_eq!(cycle(TableMode::new(StepMode::Full)), Direction::Clockwise);
+        assert_eq!(cycle(TableMode::new(StepMode::Half)), Direction::Clockwise);
+    }
+}
@@ -0,0 +1,200 @@
+use core::cell::{Cell, RefCell};
+use core::convert::Infallible;
+
+use embedded_hal::digital::{ErrorType, InputPin};
+
+/// A port which reads the level of several pins in a single transaction, such as a GPIO expander register.
+/// Bit `n` of the returned word is the level of pin `n`.
+pub trait Port {
+    /// Read the whole port
+    fn read_port(&mut self) -> u16;
+}
+
+impl<F> Port for F
+where
+    F: FnMut() -> u16,
+{
+    fn read_port(&mut self) -> u16 {
+        self()
+    }
+}
+
+/// The `consumed` bit of the DT pin
+const DT_PIN: u8 = 0x01;
+/// The `consumed` bit of the CLK pin
+const CLK_PIN: u8 = 0x02;
+
+/// PortBacked
+/// Exposes the DT and CLK bits of a single `Port` as two `InputPin`s.
+/// The port word is latched and shared: a pin reads the port only when it has already consumed the latched word,
+/// so each update reading both pins costs a single port transaction whichever pin is read first.
+pub struct PortBacked<P, const DT_BIT: u8, const CLK_BIT: u8> {
+    port: RefCell<P>,
+    latched: Cell<u16>,
+    /// One bit per pin, set once that pin has read the latched word
+    consumed: Cell<u8>,
+}
+
+impl<P, const DT_BIT: u8, const CLK_BIT: u8> PortBacked<P, DT_BIT, CLK_BIT>
+where
+    P: Port,
+{
+    /// Evaluated on construction to reject bits outside the port word at compile time
+    const VALID_BITS: () = assert!(
+        DT_BIT < 16 && CLK_BIT < 16,
+        "PortBacked DT_BIT and CLK_BIT must be below 16"
+    );
+
+    /// Initiates a new `PortBacked` adapter, `DT_BIT` and `CLK_BIT` must be below 16
+    pub fn new(port: P) -> Self {
+        let () = Self::VALID_BITS;
+        Self {
+            port: RefCell::new(port),
+            latched: Cell::new(0),
+            // Nothing is latched yet, so whichever pin reads first reads the port
+            consumed: Cell::new(DT_PIN | CLK_PIN),
+        }
+    }
+
+    /// Borrow the DT and CLK pins, ready to be passed to `RotaryEncoder::new`
+    pub fn pins(&self) -> (PortPin<'_, P>, PortPin<'_, P>) {
+        (
+            PortPin {
+                port: &self.port,
+                latched: &self.latched,
+                consumed: &self.consumed,
+                bit: DT_BIT,
+                pin: DT_PIN,
+            },
+            PortPin {
+                port: &self.port,
+                latched: &self.latched,
+                consumed: &self.consumed,
+                bit: CLK_BIT,
+                pin: CLK_PIN,
+            },
+        )
+    }
+
+    /// Release the underlying `Port`
+    pub fn release(self) -> P {
+        self.port.into_inner()
+    }
+}
+
+/// A single pin of a `PortBacked` adapter
+pub struct PortPin<'a, P> {
+    port: &'a RefCell<P>,
+    latched: &'a Cell<u16>,
+    consumed: &'a Cell<u8>,
+    bit: u8,
+    pin: u8,
+}
+
+impl<P> ErrorType for PortPin<'_, P> {
+    type Error = Infallible;
+}
+
+impl<P> InputPin for PortPin<'_, P>
+where
+    P: Port,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        if self.consumed.get() & self.pin != 0 {
+            self.latched.set(self.port.borrow_mut().read_port());
+            self.consumed.set(self.pin);
+        } else {
+            self.consumed.set(self.consumed.get() | self.pin);
+        }
+        Ok(self.latched.get() & (1 << self.bit) != 0)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_high()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RotaryEncoder;
+
+    /// A port returning a fixed word and counting the transactions
+    struct MockPort {
+        word: u16,
+        reads: usize,
+    }
+
+    impl Port for MockPort {
+        fn read_port(&mut self) -> u16 {
+            self.reads += 1;
+            self.word
+        }
+    }
+
+    #[test]
+    fn pins_expose_their_bit_of_the_port_word() {
+        let port = PortBacked::<_, 3, 9>::new(MockPort {
+            word: 1 << 3,
+            reads: 0,
+        });
+        let (mut dt, mut clk) = port.pins();
+        assert_eq!(dt.is_high(), Ok(true));
+        assert_eq!(clk.is_high(), Ok(false));
+        assert_eq!(clk.is_low(), Ok(true));
+    }
+
+    #[test]
+    fn one_port_read_per_update() {
+        let port = PortBacked::<_, 0, 1>::new(MockPort {
+            word: 0b10,
+            reads: 0,
+        });
+        {
+            let (dt, clk) = port.pins();
+            let mut encoder = RotaryEncoder::new(dt, clk).into_standard_mode();
+            for _ in 0..4 {
+                encoder.update();
+            }
+        }
+        assert_eq!(port.release().reads, 4);
+    }
+
+    #[test]
+    fn swapped_pins_stay_in_step_with_the_port() {
+        let words = [0b11, 0b01, 0b00, 0b10];
+        let mut next = words.iter().copied();
+        let port = PortBacked::<_, 0, 1>::new(move || next.next().unwrap());
+        let levels: Vec<(bool, bool)> = {
+            let (dt, clk) = port.pins();
+            let mut encoder = RotaryEncoder::new(dt, clk).into_standard_mode();
+            encoder.swap_pins();
+            (0..words.len()).map(|_| encoder.read_levels()).collect()
+        };
+        // Swapped, bit 1 is read as DT and bit 0 as CLK, one port read per update
+        assert_eq!(
+            levels,
+            vec![(true, true), (false, true), (false, false), (true, false)]
+        );
+    }
+
+    #[test]
+    fn a_lone_pin_read_is_never_stale() {
+        let mut word = 0;
+        let port = PortBacked::<_, 0, 1>::new(move || {
+            word ^= 0b10;
+            word
+        });
+        let (_, mut clk) = port.pins();
+        assert_eq!(clk.is_high(), Ok(true));
+        assert_eq!(clk.is_high(), Ok(false));
+    }
+
+    #[test]
+    fn closures_are_ports() {
+        let port = PortBacked::<_, 15, 14>::new(|| 0x8000);
+        let (mut dt, mut clk) = port.pins();
+        assert_eq!(dt.is_high(), Ok(true));
+        assert_eq!(clk.is_high(), Ok(false));
+    }
+}
@@ -1,6 +1,7 @@
 use embedded_hal::digital::InputPin;
 
-use crate::{Direction, RotaryEncoder};
+use crate::table::{NoTrace, Step, Trace};
+use crate::{Direction, Pin, PollMode, RotaryEncoder, Sensitivity};
 
 /// Quadrature Lookup Table
 /// Index = (prev_state << 2) | curr_state
@@ -24,17 +25,38 @@ const QUAD_TABLE: [i8; 16] = [
     0,  // 11 -> 11
 ];
 
-impl<DT, CLK> RotaryEncoder<QuadratureTableMode, DT, CLK>
+impl<DT, CLK, T> RotaryEncoder<QuadratureTableMode<T>, DT, CLK>
 where
     DT: InputPin,
     CLK: InputPin,
+    T: Trace,
 {
     /// Updates the `RotaryEncoder`, updating the `direction` property
     pub fn update(&mut self) -> Direction {
-        self.mode.update(
-            self.pin_dt.is_high().unwrap_or_default(),
-            self.pin_clk.is_high().unwrap_or_default(),
-        )
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Set the threshold from a `Sensitivity` preset
+    pub fn apply_sensitivity(&mut self, sensitivity: Sensitivity) {
+        self.mode.apply_sensitivity(sensitivity);
+    }
+
+    /// Updates the `RotaryEncoder`, distinguishing still pins from movement below the threshold
+    pub fn update_step(&mut self) -> Step {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_step(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder` from an edge interrupt without reading the pins,
+    /// see `QuadratureTableMode::update_edge`
+    pub fn update_edge(&mut self, changed: Pin, new_level: bool) -> Direction {
+        self.mode.update_edge(changed, new_level)
+    }
+
+    /// Report and clear any partial count below the threshold, see `QuadratureTableMode::flush`
+    pub fn flush(&mut self) -> Direction {
+        self.mode.flush()
     }
 }
 
@@ -48,11 +70,7 @@ where
         self,
         threshold: u8,
     ) -> RotaryEncoder<QuadratureTableMode, DT, CLK> {
-        RotaryEncoder {
-            pin_dt: self.pin_dt,
-            pin_clk: self.pin_clk,
-            mode: QuadratureTableMode::new(threshold),
-        }
+        self.with_mode(QuadratureTableMode::new(threshold))
     }
 }
 
@@ -62,12 +80,21 @@ impl Default for QuadratureTableMode {
     }
 }
 
+impl<T: Trace> PollMode for QuadratureTableMode<T> {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        QuadratureTableMode::update(self, dt, clk)
+    }
+}
+
 /// Quadrature Table Encoder Mode
 /// This mode is suitable for indentless encoders
-pub struct QuadratureTableMode {
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct QuadratureTableMode<T = NoTrace> {
     prev_state: u8, // lower two bits only
     threshold: u8,  // how many “deltas” before we report a step
-    count: i8,      // running sum of +1/–1 deltas
+    count: i16,     // running sum of +1/–1 deltas, wide enough for any u8 threshold
+    changed: bool,  // whether the last update saw a new state
+    trace: T,       // called on every update
 }
 
 impl QuadratureTableMode {
@@ -78,39 +105,139 @@ impl QuadratureTableMode {
             prev_state: 0,
             count: 0,
             threshold,
+            changed: false,
+            trace: NoTrace,
         }
     }
 
+    /// Initializes Quadrature table encoder from the current pin state, packed as `(clk << 1) | dt`.
+    /// `new` assumes the encoder rests at 00, which decodes a phantom step on the first update if it doesn't.
+    /// Seed from the real levels instead, so the first update only decodes genuine movement.
+    pub fn from_state(initial: u8, threshold: u8) -> Self {
+        Self {
+            prev_state: initial & 0x03,
+            count: 0,
+            threshold,
+            changed: false,
+            trace: NoTrace,
+        }
+    }
+}
+
+impl<T: Trace> QuadratureTableMode<T> {
+    /// Set the threshold from a `Sensitivity` preset, discarding any partial count
+    pub fn apply_sensitivity(&mut self, sensitivity: Sensitivity) {
+        self.threshold = match sensitivity {
+            Sensitivity::Low => 4,
+            Sensitivity::Medium => 2,
+            Sensitivity::High => 1,
+        };
+        self.count = 0;
+    }
+
+    /// Report the direction of any partial count below the threshold and reset it, `None` if there is none.
+    /// With a threshold above 1 small movements are otherwise held until more motion arrives,
+    /// call this on a timer after a period of inactivity so they don't feel sticky.
+    pub fn flush(&mut self) -> Direction {
+        let dir = if self.count > 0 {
+            Direction::Clockwise
+        } else if self.count < 0 {
+            Direction::Anticlockwise
+        } else {
+            Direction::None
+        };
+        self.count = 0;
+        dir
+    }
+
+    /// Set a hook called on every update with `(prev_state, new_state, direction)`, see `TableMode::with_trace`.
+    /// The states are packed as `(clk << 1) | dt`.
+    pub fn with_trace<U: Trace>(self, trace: U) -> QuadratureTableMode<U> {
+        QuadratureTableMode {
+            prev_state: self.prev_state,
+            threshold: self.threshold,
+            count: self.count,
+            changed: self.changed,
+            trace,
+        }
+    }
+
+    /// Remove the hook set by `with_trace`, keeping the decoding state
+    pub fn without_trace(self) -> QuadratureTableMode {
+        self.with_trace(NoTrace)
+    }
+
+    /// Returns true when the most recent update observed a pin state different from the one before it,
+    /// whether or not a Direction was yielded
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
     /// Call this on every A/B change (or in a tight loop)
     /// dt = data pin, clk = clock pin levels (0 or 1)
     pub fn update(&mut self, dt: bool, clk: bool) -> Direction {
-        let curr = (dt as u8) | ((clk as u8) << 1);
-        let idx = ((self.prev_state << 2) | curr) as usize;
+        self.decode((dt as u8) | ((clk as u8) << 1))
+    }
+
+    /// Update to determine the direction, distinguishing unchanged pins (`Step::Idle`)
+    /// from a state change which hasn't reached the threshold yet (`Step::Partial`)
+    pub fn update_step(&mut self, dt: bool, clk: bool) -> Step {
+        match self.update(dt, clk) {
+            Direction::None if self.changed => Step::Partial,
+            Direction::None => Step::Idle,
+            direction => Step::Complete(direction),
+        }
+    }
+
+    /// Update like `update`, also returning the new 2-bit state packed as `(clk << 1) | dt`,
+    /// for chaining into higher-level decoders
+    pub fn update_with_state(&mut self, dt: bool, clk: bool) -> (Direction, u8) {
+        let dir = self.update(dt, clk);
+        (dir, self.prev_state)
+    }
+
+    /// Call this from an edge interrupt which already knows the `changed` pin and its `new_level`.
+    /// Only that pin's bit of the state is replaced, the other pin keeps its last known level.
+    pub fn update_edge(&mut self, changed: Pin, new_level: bool) -> Direction {
+        let bit = match changed {
+            Pin::Dt => 0x01,
+            Pin::Clk => 0x02,
+        };
+        let curr = if new_level {
+            self.prev_state | bit
+        } else {
+            self.prev_state & !bit
+        };
+        self.decode(curr)
+    }
+
+    /// Decode the transition from `prev_state` to the packed `curr` state
+    fn decode(&mut self, curr: u8) -> Direction {
+        let prev_state = self.prev_state;
+        let idx = ((prev_state << 2) | curr) as usize;
         let delta = QUAD_TABLE[idx];
+        self.changed = curr != prev_state;
         self.prev_state = curr;
-        self.count += delta;
-        if self.count.unsigned_abs() >= self.threshold {
-            let dir = if self.count > 0 {
+        self.count = self.count.saturating_add(delta as i16);
+        let mut dir = Direction::None;
+        if self.count.unsigned_abs() >= self.threshold as u16 {
+            dir = if self.count > 0 {
                 Direction::Clockwise
             } else {
                 Direction::Anticlockwise
             };
             self.count = 0; // reset and only report once
-            return dir;
         }
-        Direction::None
+        self.trace.trace(prev_state, curr, dir);
+        dir
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::drive_sequence;
     use crate::Direction;
 
-    /// Test‑only helper; collects the result of each update.
-    fn drive_sequence(mode: &mut QuadratureTableMode, seq: &[(bool, bool)]) -> Vec<Direction> {
-        seq.iter().map(|&(dt, clk)| mode.update(dt, clk)).collect()
-    }
-
     #[test]
     fn single_cw_step_threshold_1() {
         let mut mode = QuadratureTableMode::new(1);
@@ -149,6 +276,167 @@ mod tests {
         }
     }
 
+    #[test]
+    fn changed_reports_state_changes_below_threshold() {
+        let mut mode = QuadratureTableMode::new(2);
+        mode.update(false, false);
+        assert!(!mode.changed());
+        mode.update(false, false);
+        assert!(!mode.changed());
+
+        // 00 -> 01 is below the threshold, but still a change
+        assert_eq!(mode.update(true, false), Direction::None);
+        assert!(mode.changed());
+        mode.update(true, false);
+        assert!(!mode.changed());
+    }
+
+    #[test]
+    fn sensitivity_presets_set_the_threshold() {
+        let mut mode = QuadratureTableMode::new(1);
+        for (sensitivity, threshold) in [
+            (Sensitivity::Low, 4),
+            (Sensitivity::Medium, 2),
+            (Sensitivity::High, 1),
+        ] {
+            mode.apply_sensitivity(sensitivity);
+            assert_eq!(mode.threshold, threshold);
+        }
+    }
+
+    #[test]
+    fn flush_emits_and_clears_the_partial_count() {
+        let mut mode = QuadratureTableMode::new(4);
+        // Two CW deltas, below the threshold
+        assert_eq!(mode.update(true, false), Direction::None);
+        assert_eq!(mode.update(true, true), Direction::None);
+        assert_eq!(mode.flush(), Direction::Clockwise);
+        assert_eq!(mode.count, 0);
+        assert_eq!(mode.flush(), Direction::None);
+
+        // 11 -> 01 = -1
+        assert_eq!(mode.update(true, false), Direction::None);
+        assert_eq!(mode.flush(), Direction::Anticlockwise);
+        assert_eq!(mode.count, 0);
+    }
+
+    #[test]
+    fn edge_updates_match_polled_updates() {
+        // A full CW cycle then a full CCW cycle, one pin changing at a time
+        let edges = [
+            (Pin::Dt, true),   // 00→01
+            (Pin::Clk, true),  // 01→11
+            (Pin::Dt, false),  // 11→10
+            (Pin::Clk, false), // 10→00
+            (Pin::Clk, true),  // 00→10
+            (Pin::Dt, true),   // 10→11
+            (Pin::Clk, false), // 11→01
+            (Pin::Dt, false),  // 01→00
+        ];
+
+        let mut edge = QuadratureTableMode::new(1);
+        let mut polled = QuadratureTableMode::new(1);
+        let (mut dt, mut clk) = (false, false);
+        let mut steps = Vec::new();
+        for &(pin, level) in &edges {
+            match pin {
+                Pin::Dt => dt = level,
+                Pin::Clk => clk = level,
+            }
+            let dir = edge.update_edge(pin, level);
+            assert_eq!(dir, polled.update(dt, clk));
+            steps.push(dir);
+        }
+        assert_eq!(
+            steps,
+            [[Direction::Clockwise; 4], [Direction::Anticlockwise; 4]].concat()
+        );
+    }
+
+    #[test]
+    fn large_threshold_fires_after_exactly_that_many_deltas() {
+        let mut mode = QuadratureTableMode::new(200);
+        // CW: 00→01→11→10→00
+        let cycle = [(true, false), (true, true), (false, true), (false, false)];
+        let mut deltas = cycle.iter().cycle();
+        for _ in 0..199 {
+            let &(dt, clk) = deltas.next().unwrap();
+            assert_eq!(mode.update(dt, clk), Direction::None);
+        }
+        let &(dt, clk) = deltas.next().unwrap();
+        assert_eq!(mode.update(dt, clk), Direction::Clockwise);
+        assert_eq!(mode.count, 0);
+    }
+
+    #[test]
+    fn update_with_state_returns_the_packed_state() {
+        let mut mode = QuadratureTableMode::new(1);
+        // CW: 00→01→11→10→00
+        for &(dt, clk) in &[(true, false), (true, true), (false, true), (false, false)] {
+            let (dir, state) = mode.update_with_state(dt, clk);
+            assert_eq!(dir, Direction::Clockwise);
+            assert_eq!(state, ((clk as u8) << 1) | dt as u8);
+        }
+    }
+
+    #[test]
+    fn cloned_modes_decode_independently() {
+        let mut mode = QuadratureTableMode::new(2);
+        // One CW delta, half way to the threshold
+        mode.update(true, false);
+        let mut speculative = mode;
+
+        // 01→11 completes CW, 01→00 cancels it out
+        assert_eq!(mode.update(true, true), Direction::Clockwise);
+        assert_eq!(speculative.update(false, false), Direction::None);
+        assert_ne!(mode, speculative);
+        assert_eq!(speculative.count, 0);
+    }
+
+    #[test]
+    fn update_step_reports_partial_counts_below_the_threshold() {
+        let mut mode = QuadratureTableMode::new(4);
+        assert_eq!(mode.update_step(false, false), Step::Idle);
+        // CW: 00→01→11→10→00
+        let steps: Vec<Step> = [(true, false), (true, true), (false, true), (false, false)]
+            .iter()
+            .map(|&(dt, clk)| mode.update_step(dt, clk))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                Step::Partial,
+                Step::Partial,
+                Step::Partial,
+                Step::Complete(Direction::Clockwise),
+            ]
+        );
+        assert_eq!(mode.update_step(false, false), Step::Idle);
+    }
+
+    #[test]
+    fn trace_reports_every_transition() {
+        let mut traced = Vec::new();
+        let mut mode = QuadratureTableMode::new(4)
+            .with_trace(|prev, next, dir| traced.push((prev, next, dir)));
+        // CW: 00→01→11→10→00, yielding a step on the last transition
+        for &(dt, clk) in &[(true, false), (true, true), (false, true), (false, false)] {
+            mode.update(dt, clk);
+        }
+        let mut mode = mode.without_trace();
+        assert_eq!(
+            traced,
+            vec![
+                (0b00, 0b01, Direction::None),
+                (0b01, 0b11, Direction::None),
+                (0b11, 0b10, Direction::None),
+                (0b10, 0b00, Direction::Clockwise),
+            ]
+        );
+        // Still at rest once the hook is removed
+        assert_eq!(mode.update_step(false, false), Step::Idle);
+    }
+
     #[test]
     fn invalid_transition_skipped_state() {
         let mut mode = QuadratureTableMode::new(1);
@@ -158,6 +446,24 @@ mod tests {
         assert_eq!(mode.update(false, false), Direction::None);
     }
 
+    #[test]
+    fn from_state_avoids_phantom_first_step() {
+        // Resting at 10 (clk high, dt low), `new` assumes 00 and decodes 00 -> 10 as CCW
+        assert_eq!(
+            QuadratureTableMode::new(1).update(false, true),
+            Direction::Anticlockwise
+        );
+        assert_eq!(
+            QuadratureTableMode::from_state(0b10, 1).update(false, true),
+            Direction::None
+        );
+
+        let mut mode = QuadratureTableMode::from_state(0b11, 1);
+        assert_eq!(mode.update(true, true), Direction::None);
+        // 11 -> 10 = +1
+        assert_eq!(mode.update(false, true), Direction::Clockwise);
+    }
+
     #[test]
     fn full_cw_cycle_threshold_1() {
         let mut mode = QuadratureTableMode::new(1);
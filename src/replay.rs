@@ -0,0 +1,72 @@
+use crate::{Direction, PollMode};
+
+/// Parse a CSV trace of `dt,clk` samples, one sample per line, such as a logic-analyzer export.
+/// Each level is a number, 0 is low and anything else high. Further columns are ignored.
+/// Blank lines, `#` comments and lines not starting with a number, such as a header row, are skipped.
+pub fn parse_trace(trace: &str) -> impl Iterator<Item = (bool, bool)> + '_ {
+    trace.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.starts_with('#') {
+            return None;
+        }
+        let mut fields = line.split(',').map(|field| field.trim().parse::<u8>());
+        match (fields.next(), fields.next()) {
+            (Some(Ok(dt)), Some(Ok(clk))) => Some((dt != 0, clk != 0)),
+            _ => None,
+        }
+    })
+}
+
+/// Decode a CSV trace, see `parse_trace`, with `mode` and return the net number of steps.
+/// Clockwise steps count up and Anticlockwise steps count down.
+pub fn decode_trace<MODE: PollMode>(mode: &mut MODE, trace: &str) -> i32 {
+    let mut steps = 0;
+    for (dt, clk) in parse_trace(trace) {
+        match mode.update(dt, clk) {
+            Direction::Clockwise => steps += 1,
+            Direction::Anticlockwise => steps -= 1,
+            Direction::None => {}
+        }
+    }
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standard::StandardMode;
+    use crate::table::{StepMode, TableMode};
+
+    /// Two clockwise cycles followed by one anticlockwise cycle, resting at 11
+    const TRACE: &str = "dt,clk
+# captured at 1kHz
+1,1
+0,1
+0,0
+1,0
+1,1
+
+0,1
+0,0
+1,0
+1,1
+1,0
+0,0
+0,1
+1,1
+";
+
+    #[test]
+    fn parse_trace_skips_headers_and_comments() {
+        let samples: Vec<(bool, bool)> =
+            parse_trace("dt,clk\n# comment\n0,1,ignored\n\n1, 0\n").collect();
+        assert_eq!(samples, vec![(false, true), (true, false)]);
+    }
+
+    #[test]
+    fn decode_trace_reports_the_net_step_count() {
+        assert_eq!(decode_trace(&mut TableMode::new(StepMode::Full), TRACE), 1);
+        assert_eq!(decode_trace(&mut TableMode::new(StepMode::Half), TRACE), 2);
+        assert_eq!(decode_trace(&mut StandardMode::new(), TRACE), 1);
+    }
+}
@@ -1,60 +1,158 @@
 use embedded_hal::digital::InputPin;
 
 use crate::Direction;
+use crate::PollMode;
 use crate::RotaryEncoder;
 
+/// Default number of unchanged updates before `stuck_detected` trips (~10s at 900Hz)
+const DEFAULT_STUCK_THRESHOLD: u16 = 9000;
+
 /// StandardMode
 /// This mode is best used when polled at ~900Hz.
+/// `BITS` is the number of samples of history examined per pin, an edge is recognised as `BITS / 2`
+/// samples of one level followed by `BITS / 2` samples of the other. Larger widths reject longer glitches.
+/// `BITS` must be even and between 2 and 32, it defaults to 2.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct StandardMode {
+pub struct StandardMode<const BITS: usize = 2> {
     /// The pin state
-    pin_state: [u8; 2],
+    pin_state: [u32; 2],
+    /// The number of samples where both pins changed at once
+    missed_steps: u32,
+    /// The number of consecutive updates without any pin change
+    unchanged_updates: u16,
+    /// The number of unchanged updates considered stuck
+    stuck_threshold: u16,
+    /// Whether the most recent update observed a pin change
+    changed: bool,
+    /// Whether a real sample has been seen, until then the history is only a placeholder
+    sampled: bool,
 }
 
-// For debouncing of pins, use 0x0f (b00001111) and 0x0c (b00001100) etc.
-const PIN_MASK: u8 = 0x03;
-const PIN_EDGE: u8 = 0x02;
-
-impl<DT, CLK> RotaryEncoder<StandardMode, DT, CLK>
+impl<DT, CLK, const BITS: usize> RotaryEncoder<StandardMode<BITS>, DT, CLK>
 where
     DT: InputPin,
     CLK: InputPin,
 {
     /// Updates the `RotaryEncoder`, updating the `direction` property
     pub fn update(&mut self) -> Direction {
-        self.mode.update(
-            self.pin_dt.is_high().unwrap_or_default(),
-            self.pin_clk.is_high().unwrap_or_default(),
-        )
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder`, invoking `callback` only when a step is decoded
+    pub fn update_with_cb<F: FnMut(Direction)>(&mut self, mut callback: F) {
+        let dir = self.update();
+        if dir != Direction::None {
+            callback(dir);
+        }
     }
 }
 
 impl StandardMode {
-    /// Initialises the StandardMode
+    /// Initialises the StandardMode with the default history width of 2 samples
     pub fn new() -> Self {
+        Self::with_history()
+    }
+}
+
+impl<const BITS: usize> StandardMode<BITS> {
+    /// Mask of the examined history
+    const PIN_MASK: u32 = if BITS >= 32 {
+        u32::MAX
+    } else {
+        (1 << BITS) - 1
+    };
+    /// History pattern of an edge: the upper half of the examined history high, the lower half low
+    const PIN_EDGE: u32 = Self::PIN_MASK & !(Self::PIN_MASK >> (BITS / 2));
+    /// Evaluated on construction to reject invalid widths at compile time
+    const VALID_BITS: () = assert!(
+        BITS >= 2 && BITS <= 32 && BITS & 1 == 0,
+        "StandardMode BITS must be even and between 2 and 32"
+    );
+
+    /// Initialises the StandardMode with a history width of `BITS` samples
+    pub fn with_history() -> Self {
+        let () = Self::VALID_BITS;
         Self {
-            pin_state: [0xFF, 2],
+            pin_state: [u32::MAX, 2],
+            missed_steps: 0,
+            unchanged_updates: 0,
+            stuck_threshold: DEFAULT_STUCK_THRESHOLD,
+            changed: false,
+            sampled: false,
         }
     }
 
+    /// Returns the number of samples where both pins changed since the previous sample.
+    /// A Gray-code encoder only ever changes one pin per transition, so a double change means
+    /// an intermediate state was missed because the encoder was polled too slowly.
+    pub fn missed_steps(&self) -> u32 {
+        self.missed_steps
+    }
+
+    /// Reset the `missed_steps` counter to 0
+    pub fn reset_missed_steps(&mut self) {
+        self.missed_steps = 0;
+    }
+
+    /// Set the number of consecutive updates without any pin change after which `stuck_detected` trips
+    pub fn set_stuck_threshold(&mut self, updates: u16) {
+        self.stuck_threshold = updates;
+    }
+
+    /// Returns true when the pins haven't changed for `stuck_threshold` consecutive updates.
+    /// An idle encoder also trips this, so check it when motion is expected: if the user is turning
+    /// and the levels never change, the encoder is likely disconnected or its wiring is broken.
+    pub fn stuck_detected(&self) -> bool {
+        self.unchanged_updates >= self.stuck_threshold
+    }
+
+    /// Returns true when the most recent update observed a change on either pin.
+    /// Unlike a non-`None` direction this is set by every edge, not only those completing a step.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
     /// Update to determine the direction
     pub fn update(&mut self, dt_value: bool, clk_value: bool) -> Direction {
-        self.pin_state[0] = (self.pin_state[0] << 1) | dt_value as u8;
-        self.pin_state[1] = (self.pin_state[1] << 1) | clk_value as u8;
+        self.pin_state[0] = (self.pin_state[0] << 1) | dt_value as u32;
+        self.pin_state[1] = (self.pin_state[1] << 1) | clk_value as u32;
+
+        // The first sample has nothing real to be compared against
+        if self.sampled {
+            let dt_changed = (self.pin_state[0] ^ (self.pin_state[0] >> 1)) & 1 == 1;
+            let clk_changed = (self.pin_state[1] ^ (self.pin_state[1] >> 1)) & 1 == 1;
+            if dt_changed && clk_changed {
+                self.missed_steps = self.missed_steps.saturating_add(1);
+            }
+            self.changed = dt_changed || clk_changed;
+            if self.changed {
+                self.unchanged_updates = 0;
+            } else {
+                self.unchanged_updates = self.unchanged_updates.saturating_add(1);
+            }
+        }
+        self.sampled = true;
 
-        let a = self.pin_state[0] & PIN_MASK;
-        let b = self.pin_state[1] & PIN_MASK;
+        let a = self.pin_state[0] & Self::PIN_MASK;
+        let b = self.pin_state[1] & Self::PIN_MASK;
 
         let mut dir: Direction = Direction::None;
 
-        if a == PIN_EDGE && b == 0x00 {
+        if a == Self::PIN_EDGE && b == 0x00 {
             dir = Direction::Anticlockwise;
-        } else if b == PIN_EDGE && a == 0x00 {
+        } else if b == Self::PIN_EDGE && a == 0x00 {
             dir = Direction::Clockwise;
         }
 
         dir
     }
+
+    /// Update from analog readings of the pins, such as ADC samples of an analog quadrature sensor.
+    /// A reading at or above `threshold` is taken as high, then decoded like `update`.
+    pub fn update_analog(&mut self, dt_value: u16, clk_value: u16, threshold: u16) -> Direction {
+        self.update(dt_value >= threshold, clk_value >= threshold)
+    }
 }
 
 impl<LOGIC, DT, CLK> RotaryEncoder<LOGIC, DT, CLK>
@@ -64,16 +162,187 @@ where
 {
     /// Configure `RotaryEncoder` to use the standard API
     pub fn into_standard_mode(self) -> RotaryEncoder<StandardMode, DT, CLK> {
-        RotaryEncoder {
-            pin_dt: self.pin_dt,
-            pin_clk: self.pin_clk,
-            mode: StandardMode::new(),
-        }
+        self.with_mode(StandardMode::new())
+    }
+
+    /// Configure `RotaryEncoder` to use the standard API with a history width of `BITS` samples
+    pub fn into_standard_mode_with_history<const BITS: usize>(
+        self,
+    ) -> RotaryEncoder<StandardMode<BITS>, DT, CLK> {
+        self.with_mode(StandardMode::with_history())
     }
 }
 
-impl Default for StandardMode {
+impl<const BITS: usize> Default for StandardMode<BITS> {
     fn default() -> Self {
-        Self::new()
+        Self::with_history()
+    }
+}
+
+impl<const BITS: usize> PollMode for StandardMode<BITS> {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        StandardMode::update(self, dt, clk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+
+    #[test]
+    fn update_with_cb_only_calls_back_on_steps() {
+        // CW, idle, CCW, idle
+        let levels = [(0, 1), (0, 0), (0, 0), (1, 0), (0, 0), (0, 0)];
+        let get = |level| Transaction::get(if level == 1 { State::High } else { State::Low });
+        let dt = Mock::new(&levels.iter().map(|&(dt, _)| get(dt)).collect::<Vec<_>>());
+        let clk = Mock::new(&levels.iter().map(|&(_, clk)| get(clk)).collect::<Vec<_>>());
+
+        let mut encoder = RotaryEncoder::new(dt, clk).into_standard_mode();
+        let mut steps = Vec::new();
+        for _ in 0..levels.len() {
+            encoder.update_with_cb(|dir| steps.push(dir));
+        }
+        assert_eq!(steps, vec![Direction::Clockwise, Direction::Anticlockwise]);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn adjacent_transitions_miss_nothing() {
+        let mut mode = StandardMode::new();
+        // A full Gray-code cycle: 10→00→01→11→10
+        for &(dt, clk) in &[(false, false), (false, true), (true, true), (true, false)] {
+            mode.update(dt, clk);
+        }
+        assert_eq!(mode.missed_steps(), 0);
+    }
+
+    #[test]
+    fn non_adjacent_jump_counts_a_missed_step() {
+        let mut mode = StandardMode::new();
+        mode.update(false, false);
+        // 00→11 skips either 01 or 10
+        mode.update(true, true);
+        assert_eq!(mode.missed_steps(), 1);
+        // 11→00 skips again
+        mode.update(false, false);
+        assert_eq!(mode.missed_steps(), 2);
+
+        mode.reset_missed_steps();
+        assert_eq!(mode.missed_steps(), 0);
+    }
+
+    #[test]
+    fn first_sample_is_not_compared_against_the_initial_history() {
+        let mut mode = StandardMode::new();
+        // Resting at dt=0, clk=1 before any motion
+        mode.update(false, true);
+        assert_eq!(mode.missed_steps(), 0);
+        assert!(!mode.changed());
+
+        // The next sample is compared as usual, 01→10 skips a state
+        mode.update(true, false);
+        assert_eq!(mode.missed_steps(), 1);
+        assert!(mode.changed());
+    }
+
+    #[test]
+    fn constant_levels_trip_stuck_detection() {
+        let mut mode = StandardMode::new();
+        mode.set_stuck_threshold(100);
+        mode.update(true, true);
+        for _ in 0..99 {
+            mode.update(true, true);
+        }
+        assert!(!mode.stuck_detected());
+        mode.update(true, true);
+        assert!(mode.stuck_detected());
+
+        // Any movement clears it
+        mode.update(false, true);
+        assert!(!mode.stuck_detected());
+    }
+
+    #[test]
+    fn changed_tracks_pin_changes_not_steps() {
+        let mut mode = StandardMode::new();
+        mode.update(true, true);
+        mode.update(true, true);
+        assert!(!mode.changed());
+        mode.update(true, true);
+        assert!(!mode.changed());
+
+        // An edge which doesn't complete a step
+        assert_eq!(mode.update(false, true), Direction::None);
+        assert!(mode.changed());
+        mode.update(false, true);
+        assert!(!mode.changed());
+    }
+
+    #[test]
+    fn analog_updates_match_digital_updates() {
+        let threshold = 2048;
+        // CW, idle, CCW, idle, with readings straddling the threshold
+        let samples = [(100, 4000), (100, 2047), (2048, 0), (1500, 900), (3000, 10)];
+        let mut analog = StandardMode::new();
+        let mut digital = StandardMode::new();
+        for &(dt, clk) in &samples {
+            assert_eq!(
+                analog.update_analog(dt, clk, threshold),
+                digital.update(dt >= threshold, clk >= threshold)
+            );
+        }
+
+        let mut mode = StandardMode::new();
+        mode.update_analog(0, 4095, threshold);
+        assert_eq!(mode.update_analog(0, 0, threshold), Direction::Clockwise);
+        mode.update_analog(4095, 0, threshold);
+        assert_eq!(
+            mode.update_analog(0, 0, threshold),
+            Direction::Anticlockwise
+        );
+    }
+
+    /// Feeds `dt` levels with CLK held low and returns the index of every step
+    fn steps_at<const BITS: usize>(dt: &[u8]) -> Vec<usize> {
+        let mut mode = StandardMode::<BITS>::with_history();
+        dt.iter()
+            .enumerate()
+            .filter(|&(_, &level)| mode.update(level == 1, false) != Direction::None)
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    #[test]
+    fn history_width_2_steps_on_any_falling_sample() {
+        assert_eq!(steps_at::<2>(&[1, 1, 1, 1, 0, 0, 0, 0]), vec![4]);
+        // A single-sample glitch is reported
+        assert_eq!(steps_at::<2>(&[1, 1, 1, 1, 0, 1, 1, 1]), vec![4]);
+    }
+
+    #[test]
+    fn history_width_4_rejects_single_sample_glitches() {
+        assert_eq!(steps_at::<4>(&[1, 1, 1, 1, 0, 0, 0, 0]), vec![5]);
+        assert_eq!(steps_at::<4>(&[1, 1, 1, 1, 0, 1, 1, 1]), vec![]);
+        // A two-sample glitch is still reported
+        assert_eq!(steps_at::<4>(&[1, 1, 1, 1, 0, 0, 1, 1]), vec![5]);
+    }
+
+    #[test]
+    fn history_width_8_rejects_longer_glitches() {
+        assert_eq!(steps_at::<8>(&[1, 1, 1, 1, 0, 0, 0, 0, 0]), vec![7]);
+        assert_eq!(steps_at::<8>(&[1, 1, 1, 1, 0, 0, 1, 1, 1]), vec![]);
+        assert_eq!(steps_at::<8>(&[1, 1, 1, 1, 0, 0, 0, 1, 1]), vec![]);
+    }
+
+    #[test]
+    fn edge_patterns() {
+        assert_eq!(StandardMode::<2>::PIN_EDGE, 0b10);
+        assert_eq!(StandardMode::<4>::PIN_EDGE, 0b1100);
+        assert_eq!(StandardMode::<8>::PIN_EDGE, 0xF0);
+        assert_eq!(StandardMode::<32>::PIN_EDGE, 0xFFFF_0000);
     }
 }
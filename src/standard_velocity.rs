@@ -0,0 +1,172 @@
+use embedded_hal::digital::InputPin;
+
+use crate::angular_velocity::Velocity;
+use crate::standard::StandardMode;
+use crate::{Direction, PollMode, RotaryEncoder};
+
+/// Default velocity increasing factor, applied per step
+const DEFAULT_VELOCITY_INC_FACTOR: f32 = 0.2;
+/// Default velocity decreasing factor, applied per poll
+const DEFAULT_VELOCITY_DEC_FACTOR: f32 = 0.002;
+/// Default number of polls within which a following step increases the velocity (~50ms at 900Hz)
+const DEFAULT_VELOCITY_WINDOW_POLLS: u16 = 45;
+
+/// StandardVelocityMode
+/// The `StandardMode` decoder with a velocity estimated from the density of steps across polls,
+/// no clock is needed. Like `StandardMode` this is best used when polled at ~900Hz.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StandardVelocityMode {
+    /// The underlying decoder
+    standard: StandardMode,
+    /// The estimated velocity
+    velocity: f32,
+    /// The increasing factor
+    velocity_inc_factor: f32,
+    /// The decreasing factor
+    velocity_dec_factor: f32,
+    /// The window of polls
+    velocity_window_polls: u16,
+    /// The number of polls since the last step
+    polls_since_step: u16,
+}
+
+impl<DT, CLK> RotaryEncoder<StandardVelocityMode, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Set the velocity_inc_factor. How much each closely-spaced step increases the velocity.
+    pub fn set_velocity_inc_factor(&mut self, inc_factor: f32) {
+        self.mode.velocity_inc_factor = inc_factor;
+    }
+
+    /// Set the velocity_dec_factor. How much the velocity decreases on every poll
+    pub fn set_velocity_dec_factor(&mut self, dec_factor: f32) {
+        self.mode.velocity_dec_factor = dec_factor;
+    }
+
+    /// Set the velocity_window_polls. The number of polls within which a following step increases the velocity
+    pub fn set_velocity_window_polls(&mut self, window_polls: u16) {
+        self.mode.velocity_window_polls = window_polls;
+    }
+
+    /// Updates the `RotaryEncoder`, updating the `direction` property and the velocity
+    pub fn update(&mut self) -> Direction {
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Returns the estimated velocity of the RotaryEncoder
+    pub fn velocity(&self) -> Velocity {
+        self.mode.velocity()
+    }
+}
+
+impl StandardVelocityMode {
+    /// Initialises the StandardVelocityMode
+    pub fn new() -> Self {
+        Self {
+            standard: StandardMode::new(),
+            velocity: 0.0,
+            velocity_inc_factor: DEFAULT_VELOCITY_INC_FACTOR,
+            velocity_dec_factor: DEFAULT_VELOCITY_DEC_FACTOR,
+            velocity_window_polls: DEFAULT_VELOCITY_WINDOW_POLLS,
+            polls_since_step: u16::MAX,
+        }
+    }
+
+    /// Update to determine the direction, every call counts as one poll
+    pub fn update(&mut self, dt_value: bool, clk_value: bool) -> Direction {
+        let dir = self.standard.update(dt_value, clk_value);
+
+        if dir != Direction::None {
+            if self.polls_since_step < self.velocity_window_polls {
+                self.velocity += self.velocity_inc_factor;
+                if self.velocity > 1.0 {
+                    self.velocity = 1.0;
+                }
+            }
+            self.polls_since_step = 0;
+        } else {
+            self.polls_since_step = self.polls_since_step.saturating_add(1);
+            self.velocity -= self.velocity_dec_factor;
+            if self.velocity < 0.0 {
+                self.velocity = 0.0;
+            }
+        }
+
+        dir
+    }
+
+    /// Returns the estimated velocity, between 0.0 and 1.0
+    pub fn velocity(&self) -> Velocity {
+        Velocity::new(self.velocity)
+    }
+}
+
+impl Default for StandardVelocityMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PollMode for StandardVelocityMode {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        StandardVelocityMode::update(self, dt, clk)
+    }
+}
+
+impl<LOGIC, DT, CLK> RotaryEncoder<LOGIC, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Configure `RotaryEncoder` to use the StandardVelocityMode API
+    pub fn into_standard_velocity_mode(self) -> RotaryEncoder<StandardVelocityMode, DT, CLK> {
+        self.with_mode(StandardVelocityMode::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Polls `steps` clockwise detents spaced `period` polls apart
+    fn spin(mode: &mut StandardVelocityMode, steps: usize, period: usize) -> usize {
+        let mut seen = 0;
+        for _ in 0..steps {
+            mode.update(false, true);
+            for _ in 1..period {
+                if mode.update(false, false) == Direction::Clockwise {
+                    seen += 1;
+                }
+            }
+        }
+        seen
+    }
+
+    #[test]
+    fn dense_steps_raise_the_velocity() {
+        let mut mode = StandardVelocityMode::new();
+        assert_eq!(spin(&mut mode, 8, 4), 8);
+        assert!(*mode.velocity() > 0.9);
+    }
+
+    #[test]
+    fn sparse_steps_keep_the_velocity_idle() {
+        let mut mode = StandardVelocityMode::new();
+        assert_eq!(spin(&mut mode, 8, 100), 8);
+        assert_eq!(*mode.velocity(), 0.0);
+    }
+
+    #[test]
+    fn velocity_decays_while_idle() {
+        let mut mode = StandardVelocityMode::new();
+        spin(&mut mode, 8, 4);
+        let fast = mode.velocity();
+        for _ in 0..100 {
+            mode.update(false, false);
+        }
+        assert!(mode.velocity() < fast);
+    }
+}
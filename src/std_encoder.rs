@@ -0,0 +1,131 @@
+use std::time::Instant;
+
+use embedded_hal::digital::InputPin;
+
+use crate::angular_velocity::{AngularVelocityMode, Velocity};
+use crate::{Direction, RotaryEncoder};
+
+/// StdEncoder
+/// A `RotaryEncoder` in `AngularVelocityMode` which reads timestamps from `std::time::Instant`,
+/// so that updates don't need the current time passed in. Intended for host-side prototyping and simulation.
+pub struct StdEncoder<DT, CLK> {
+    encoder: RotaryEncoder<AngularVelocityMode, DT, CLK>,
+    epoch: Instant,
+}
+
+impl<DT, CLK> StdEncoder<DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Initiates a new `StdEncoder`, taking two InputPins
+    pub fn new(pin_dt: DT, pin_clk: CLK) -> Self {
+        Self {
+            encoder: RotaryEncoder::new(pin_dt, pin_clk).into_angular_velocity_mode(),
+            epoch: Instant::now(),
+        }
+    }
+
+    /// Update the underlying `RotaryEncoder` with the milliseconds elapsed since construction
+    pub fn update(&mut self) -> Direction {
+        let millis = self.epoch.elapsed().as_millis() as u64;
+        self.encoder.update(millis)
+    }
+
+    /// Reduce the angular velocity, see `RotaryEncoder::decay_velocity`
+    pub fn decay_velocity(&mut self) {
+        self.encoder.decay_velocity();
+    }
+
+    /// Returns the current angular velocity
+    pub fn velocity(&self) -> Velocity {
+        self.encoder.velocity()
+    }
+
+    /// Borrow the underlying `RotaryEncoder`, for example to configure the velocity factors
+    pub fn encoder(&mut self) -> &mut RotaryEncoder<AngularVelocityMode, DT, CLK> {
+        &mut self.encoder
+    }
+
+    /// Release the underying InputPins
+    pub fn release(self) -> (DT, CLK) {
+        self.encoder.release()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    /// Builds pins replaying `detents` clockwise detents, two updates per detent
+    fn cw_pins(detents: usize) -> (Mock, Mock) {
+        let mut dt = Vec::new();
+        let mut clk = Vec::new();
+        for _ in 0..detents {
+            dt.extend([Transaction::get(State::Low), Transaction::get(State::Low)]);
+            clk.extend([Transaction::get(State::High), Transaction::get(State::Low)]);
+        }
+        (Mock::new(&dt), Mock::new(&clk))
+    }
+
+    #[test]
+    fn velocity_builds_when_spinning_fast_and_decays() {
+        let (dt, clk) = cw_pins(5);
+        let mut encoder = StdEncoder::new(dt, clk);
+        for _ in 0..5 {
+            encoder.update();
+            assert_eq!(encoder.update(), Direction::Clockwise);
+        }
+        assert!(*encoder.velocity() > 0.0);
+
+        for _ in 0..200 {
+            encoder.decay_velocity();
+        }
+        assert_eq!(*encoder.velocity(), 0.0);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn velocity_resets_after_resting_in_real_time() {
+        let (dt, clk) = cw_pins(6);
+        let mut encoder = StdEncoder::new(dt, clk);
+        encoder.encoder().set_reset_on_detent(true);
+        for _ in 0..5 {
+            encoder.update();
+            assert_eq!(encoder.update(), Direction::Clockwise);
+        }
+        assert!(*encoder.velocity() > 0.0);
+
+        // Resting past the action window, the next update finds no step and resets the velocity
+        sleep(Duration::from_millis(30));
+        assert_eq!(encoder.update(), Direction::None);
+        assert_eq!(*encoder.velocity(), 0.0);
+        assert_eq!(encoder.update(), Direction::Clockwise);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+
+    #[test]
+    fn velocity_stays_idle_when_turning_slowly() {
+        let (dt, clk) = cw_pins(3);
+        let mut encoder = StdEncoder::new(dt, clk);
+        for _ in 0..3 {
+            encoder.update();
+            sleep(Duration::from_millis(30));
+            assert_eq!(encoder.update(), Direction::Clockwise);
+        }
+        assert_eq!(*encoder.velocity(), 0.0);
+
+        let (mut dt, mut clk) = encoder.release();
+        dt.done();
+        clk.done();
+    }
+}
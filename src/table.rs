@@ -0,0 +1,510 @@
+use embedded_hal::digital::InputPin;
+
+use crate::{Direction, PollMode, RotaryEncoder};
+
+/// Initial state of every state table, the encoder rests here between detents
+pub const R_START: u8 = 0x0;
+/// Flag set on a table entry which completes a Clockwise step
+pub const DIR_CW: u8 = 0x10;
+/// Flag set on a table entry which completes an Anticlockwise step
+pub const DIR_CCW: u8 = 0x20;
+/// Mask of the next-state bits of a table entry
+const STATE_MASK: u8 = 0x0F;
+
+// Full-step states
+const R_CW_FINAL: u8 = 0x1;
+const R_CW_BEGIN: u8 = 0x2;
+const R_CW_NEXT: u8 = 0x3;
+const R_CCW_BEGIN: u8 = 0x4;
+const R_CCW_FINAL: u8 = 0x5;
+const R_CCW_NEXT: u8 = 0x6;
+
+/// Full-step state table, yields one step per full quadrature cycle.
+/// Rows are states, columns are indexed by `(dt << 1) | clk` and entries are the next state with optional direction flags.
+pub const STATE_TABLE_FULL_STEPS: [[u8; 4]; 7] = [
+    // R_START
+    [R_START, R_CW_BEGIN, R_CCW_BEGIN, R_START],
+    // R_CW_FINAL
+    [R_CW_NEXT, R_START, R_CW_FINAL, R_START | DIR_CW],
+    // R_CW_BEGIN
+    [R_CW_NEXT, R_CW_BEGIN, R_START, R_START],
+    // R_CW_NEXT
+    [R_CW_NEXT, R_CW_BEGIN, R_CW_FINAL, R_START],
+    // R_CCW_BEGIN
+    [R_CCW_NEXT, R_START, R_CCW_BEGIN, R_START],
+    // R_CCW_FINAL
+    [R_CCW_NEXT, R_CCW_FINAL, R_START, R_START | DIR_CCW],
+    // R_CCW_NEXT
+    [R_CCW_NEXT, R_CCW_FINAL, R_CCW_BEGIN, R_START],
+];
+
+// Half-step states
+const R_CCW_BEGIN_H: u8 = 0x1;
+const R_CW_BEGIN_H: u8 = 0x2;
+const R_START_M: u8 = 0x3;
+const R_CW_BEGIN_M: u8 = 0x4;
+const R_CCW_BEGIN_M: u8 = 0x5;
+
+/// Half-step state table, yields two steps per full quadrature cycle.
+/// Rows are states, columns are indexed by `(dt << 1) | clk` and entries are the next state with optional direction flags.
+pub const STATE_TABLE_HALF_STEPS: [[u8; 4]; 6] = [
+    // R_START (11)
+    [R_START_M, R_CW_BEGIN_H, R_CCW_BEGIN_H, R_START],
+    // R_CCW_BEGIN
+    [R_START_M | DIR_CCW, R_START, R_CCW_BEGIN_H, R_START],
+    // R_CW_BEGIN
+    [R_START_M | DIR_CW, R_CW_BEGIN_H, R_START, R_START],
+    // R_START_M (00)
+    [R_START_M, R_CCW_BEGIN_M, R_CW_BEGIN_M, R_START],
+    // R_CW_BEGIN_M
+    [R_START_M, R_START_M, R_CW_BEGIN_M, R_START | DIR_CW],
+    // R_CCW_BEGIN_M
+    [R_START_M, R_CCW_BEGIN_M, R_START_M, R_START | DIR_CCW],
+];
+
+/// The decoding resolution of a `TableMode`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum StepMode {
+    /// One step per full quadrature cycle, using `STATE_TABLE_FULL_STEPS`
+    Full,
+    /// Two steps per full quadrature cycle, using `STATE_TABLE_HALF_STEPS`
+    Half,
+}
+
+/// The first malformed entry found when validating a custom state table
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TableError {
+    /// The table has no states
+    Empty,
+    /// The table has more states than fit in the 4 next-state bits
+    TooManyStates,
+    /// The entry at `table[state][input]` points to a next state outside the table
+    StateOutOfRange {
+        /// Row of the malformed entry
+        state: u8,
+        /// Column of the malformed entry
+        input: u8,
+    },
+    /// The entry at `table[state][input]` sets both direction flags, or bits outside the state and flags
+    InvalidFlags {
+        /// Row of the malformed entry
+        state: u8,
+        /// Column of the malformed entry
+        input: u8,
+    },
+}
+
+/// Check that `table` is a well-formed state table: at most 16 states, every next state inside the table
+/// and at most one direction flag per entry. Being a `const fn`, this can validate a table at compile time.
+pub const fn validate_table(table: &[[u8; 4]]) -> Result<(), TableError> {
+    if table.is_empty() {
+        return Err(TableError::Empty);
+    }
+    if table.len() > STATE_MASK as usize + 1 {
+        return Err(TableError::TooManyStates);
+    }
+    let mut state = 0;
+    while state < table.len() {
+        let mut input = 0;
+        while input < 4 {
+            let entry = table[state][input];
+            if entry & !(STATE_MASK | DIR_CW | DIR_CCW) != 0
+                || entry & (DIR_CW | DIR_CCW) == DIR_CW | DIR_CCW
+            {
+                return Err(TableError::InvalidFlags {
+                    state: state as u8,
+                    input: input as u8,
+                });
+            }
+            if (entry & STATE_MASK) as usize >= table.len() {
+                return Err(TableError::StateOutOfRange {
+                    state: state as u8,
+                    input: input as u8,
+                });
+            }
+            input += 1;
+        }
+        state += 1;
+    }
+    Ok(())
+}
+
+/// The detailed result of a `TableMode` update
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UpdateResult {
+    /// The decoded direction
+    pub direction: Direction,
+    /// True when the step ended back at rest in `R_START`, i.e. a full detent completed
+    pub detent_complete: bool,
+}
+
+/// The progress of a table update, separating still pins from movement within a detent
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+    /// The state machine didn't advance, the pins are still or bounced within the same state
+    Idle,
+    /// The state machine advanced without reaching a direction-yielding state, e.g. mid-detent
+    Partial,
+    /// A step completed in the given direction
+    Complete(Direction),
+}
+
+/// A hook called on every update of a table based mode, for debugging flaky encoders.
+/// Any `FnMut(prev_state, new_state, direction)` closure is a `Trace`, so it can capture a buffer or counter.
+pub trait Trace {
+    /// Called with the state before and after the update, and the direction it yielded
+    fn trace(&mut self, prev_state: u8, new_state: u8, direction: Direction);
+}
+
+impl<F: FnMut(u8, u8, Direction)> Trace for F {
+    fn trace(&mut self, prev_state: u8, new_state: u8, direction: Direction) {
+        self(prev_state, new_state, direction)
+    }
+}
+
+/// The default `Trace`, which does nothing and compiles away
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NoTrace;
+
+impl Trace for NoTrace {
+    #[inline(always)]
+    fn trace(&mut self, _prev_state: u8, _new_state: u8, _direction: Direction) {}
+}
+
+/// TableMode
+/// Decodes using a state table, the resolution can be switched between full and half steps at runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TableMode<T = NoTrace> {
+    /// The state table being indexed
+    table: &'static [[u8; 4]],
+    /// The current state of the table
+    table_state: u8,
+    /// Called on every update with the previous state, the new state and the direction
+    trace: T,
+}
+
+impl<DT, CLK, T> RotaryEncoder<TableMode<T>, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+    T: Trace,
+{
+    /// Updates the `RotaryEncoder`, updating the `direction` property
+    pub fn update(&mut self) -> Direction {
+        let (dt, clk) = self.read_levels();
+        self.mode.update(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder`, also reporting whether a full detent completed
+    pub fn update_detailed(&mut self) -> UpdateResult {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_detailed(dt, clk)
+    }
+
+    /// Updates the `RotaryEncoder`, distinguishing still pins from movement part way through a step
+    pub fn update_step(&mut self) -> Step {
+        let (dt, clk) = self.read_levels();
+        self.mode.update_step(dt, clk)
+    }
+}
+
+impl<LOGIC, DT, CLK> RotaryEncoder<LOGIC, DT, CLK>
+where
+    DT: InputPin,
+    CLK: InputPin,
+{
+    /// Configure `RotaryEncoder` to use the table mode
+    pub fn into_table_mode(self, step_mode: StepMode) -> RotaryEncoder<TableMode, DT, CLK> {
+        self.with_mode(TableMode::new(step_mode))
+    }
+}
+
+impl Default for TableMode {
+    fn default() -> Self {
+        Self::new(StepMode::Full)
+    }
+}
+
+impl<T: Trace> PollMode for TableMode<T> {
+    fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        TableMode::update(self, dt, clk)
+    }
+}
+
+impl TableMode {
+    /// Initializes the TableMode with the given `step_mode`
+    pub fn new(step_mode: StepMode) -> Self {
+        let mut mode = Self {
+            table: &STATE_TABLE_FULL_STEPS,
+            table_state: R_START,
+            trace: NoTrace,
+        };
+        mode.set_step_mode(step_mode);
+        mode
+    }
+
+    /// Initializes the TableMode with a custom state table, laid out like `STATE_TABLE_FULL_STEPS`,
+    /// returning the first malformed entry if the table fails `validate_table`
+    pub fn with_table_checked(table: &'static [[u8; 4]]) -> Result<Self, TableError> {
+        match validate_table(table) {
+            Ok(()) => Ok(Self {
+                table,
+                table_state: R_START,
+                trace: NoTrace,
+            }),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<T: Trace> TableMode<T> {
+    /// Switch the table being decoded and reset the `table_state`
+    pub fn set_step_mode(&mut self, step_mode: StepMode) {
+        self.table = match step_mode {
+            StepMode::Full => &STATE_TABLE_FULL_STEPS,
+            StepMode::Half => &STATE_TABLE_HALF_STEPS,
+        };
+        self.table_state = R_START;
+    }
+
+    /// Set a hook called on every update with `(prev_state, new_state, direction)`, for debugging flaky encoders.
+    /// The states exclude the direction flags. The default `NoTrace` hook compiles away, so untraced modes pay nothing.
+    pub fn with_trace<U: Trace>(self, trace: U) -> TableMode<U> {
+        TableMode {
+            table: self.table,
+            table_state: self.table_state,
+            trace,
+        }
+    }
+
+    /// Remove the hook set by `with_trace`, keeping the decoding state
+    pub fn without_trace(self) -> TableMode {
+        self.with_trace(NoTrace)
+    }
+
+    /// Update to determine the direction
+    pub fn update(&mut self, dt: bool, clk: bool) -> Direction {
+        self.update_detailed(dt, clk).direction
+    }
+
+    /// Update to determine the direction, distinguishing an update which left the state machine where it was
+    /// from one which advanced it part way through a step
+    pub fn update_step(&mut self, dt: bool, clk: bool) -> Step {
+        let prev_state = self.table_state & STATE_MASK;
+        match self.update_detailed(dt, clk).direction {
+            Direction::None if self.table_state & STATE_MASK == prev_state => Step::Idle,
+            Direction::None => Step::Partial,
+            direction => Step::Complete(direction),
+        }
+    }
+
+    /// Update to determine the direction and whether it completed a full detent.
+    /// In half-step decoding only every second step completes a detent.
+    pub fn update_detailed(&mut self, dt: bool, clk: bool) -> UpdateResult {
+        let input = ((dt as usize) << 1) | clk as usize;
+        let prev_state = self.table_state & STATE_MASK;
+        let entry = self.table[prev_state as usize][input];
+        self.table_state = entry;
+        let direction = match entry & (DIR_CW | DIR_CCW) {
+            DIR_CW => Direction::Clockwise,
+            DIR_CCW => Direction::Anticlockwise,
+            _ => Direction::None,
+        };
+        self.trace.trace(prev_state, entry & STATE_MASK, direction);
+        UpdateResult {
+            direction,
+            detent_complete: direction != Direction::None && entry & STATE_MASK == R_START,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drive_sequence;
+
+    /// A full clockwise quadrature cycle starting and ending at rest (11)
+    const CW_CYCLE: [(bool, bool); 4] =
+        [(false, true), (false, false), (true, false), (true, true)];
+    /// A full anticlockwise quadrature cycle starting and ending at rest (11)
+    const CCW_CYCLE: [(bool, bool); 4] =
+        [(true, false), (false, false), (false, true), (true, true)];
+
+    /// Returns the net number of steps decoded over `cycles` repetitions of `seq`
+    fn count_steps<T: Trace>(mode: &mut TableMode<T>, seq: &[(bool, bool)], cycles: usize) -> i32 {
+        let mut steps = 0;
+        for _ in 0..cycles {
+            for dir in drive_sequence(mode, seq) {
+                dir.apply(&mut steps, 1);
+            }
+        }
+        steps
+    }
+
+    #[test]
+    fn full_steps_yield_one_step_per_cycle() {
+        let mut mode = TableMode::new(StepMode::Full);
+        assert_eq!(count_steps(&mut mode, &CW_CYCLE, 3), 3);
+        assert_eq!(count_steps(&mut mode, &CCW_CYCLE, 2), -2);
+    }
+
+    #[test]
+    fn half_steps_yield_two_steps_per_cycle() {
+        let mut mode = TableMode::new(StepMode::Half);
+        assert_eq!(count_steps(&mut mode, &CW_CYCLE, 3), 6);
+        assert_eq!(count_steps(&mut mode, &CCW_CYCLE, 2), -4);
+    }
+
+    #[test]
+    fn update_step_separates_partial_from_idle() {
+        let mut mode = TableMode::new(StepMode::Full);
+        assert_eq!(mode.update_step(true, true), Step::Idle);
+        let steps: Vec<Step> = CW_CYCLE
+            .iter()
+            .map(|&(dt, clk)| mode.update_step(dt, clk))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                Step::Partial,
+                Step::Partial,
+                Step::Partial,
+                Step::Complete(Direction::Clockwise),
+            ]
+        );
+        assert_eq!(mode.update_step(true, true), Step::Idle);
+
+        let mut mode = TableMode::new(StepMode::Half);
+        let steps: Vec<Step> = CCW_CYCLE
+            .iter()
+            .map(|&(dt, clk)| mode.update_step(dt, clk))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                Step::Partial,
+                Step::Complete(Direction::Anticlockwise),
+                Step::Partial,
+                Step::Complete(Direction::Anticlockwise),
+            ]
+        );
+    }
+
+    #[test]
+    fn detent_completes_at_the_end_of_a_full_step_cycle() {
+        let mut mode = TableMode::new(StepMode::Full);
+        let results: Vec<UpdateResult> = CW_CYCLE
+            .iter()
+            .map(|&(dt, clk)| mode.update_detailed(dt, clk))
+            .collect();
+        let completes: Vec<bool> = results.iter().map(|r| r.detent_complete).collect();
+        assert_eq!(completes, vec![false, false, false, true]);
+        assert_eq!(results[3].direction, Direction::Clockwise);
+    }
+
+    #[test]
+    fn half_step_mid_cycle_step_is_not_a_detent() {
+        let mut mode = TableMode::new(StepMode::Half);
+        let results: Vec<UpdateResult> = CCW_CYCLE
+            .iter()
+            .map(|&(dt, clk)| mode.update_detailed(dt, clk))
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                UpdateResult {
+                    direction: Direction::None,
+                    detent_complete: false
+                },
+                UpdateResult {
+                    direction: Direction::Anticlockwise,
+                    detent_complete: false
+                },
+                UpdateResult {
+                    direction: Direction::None,
+                    detent_complete: false
+                },
+                UpdateResult {
+                    direction: Direction::Anticlockwise,
+                    detent_complete: true
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn built_in_tables_are_valid() {
+        const _: () = assert!(validate_table(&STATE_TABLE_FULL_STEPS).is_ok());
+        const _: () = assert!(validate_table(&STATE_TABLE_HALF_STEPS).is_ok());
+        assert!(TableMode::with_table_checked(&STATE_TABLE_HALF_STEPS).is_ok());
+    }
+
+    #[test]
+    fn custom_table_decodes() {
+        static TWO_STATE: [[u8; 4]; 2] = [[0, 1, 0, 0], [0, 1, 0, R_START | DIR_CW]];
+        let mut mode = TableMode::with_table_checked(&TWO_STATE).unwrap();
+        assert_eq!(mode.update(false, true), Direction::None);
+        assert_eq!(mode.update(true, true), Direction::Clockwise);
+    }
+
+    #[test]
+    fn malformed_tables_are_rejected() {
+        static EMPTY: [[u8; 4]; 0] = [];
+        static OUT_OF_RANGE: [[u8; 4]; 2] = [[0, 1, 0, 0], [0, 0, 2, 0]];
+        static BOTH_FLAGS: [[u8; 4]; 1] = [[0, 0, 0, DIR_CW | DIR_CCW]];
+        static STRAY_BITS: [[u8; 4]; 1] = [[0x40, 0, 0, 0]];
+        static TOO_MANY: [[u8; 4]; 17] = [[0; 4]; 17];
+
+        assert_eq!(
+            TableMode::with_table_checked(&EMPTY),
+            Err(TableError::Empty)
+        );
+        assert_eq!(
+            TableMode::with_table_checked(&OUT_OF_RANGE),
+            Err(TableError::StateOutOfRange { state: 1, input: 2 })
+        );
+        assert_eq!(
+            TableMode::with_table_checked(&BOTH_FLAGS),
+            Err(TableError::InvalidFlags { state: 0, input: 3 })
+        );
+        assert_eq!(
+            TableMode::with_table_checked(&STRAY_BITS),
+            Err(TableError::InvalidFlags { state: 0, input: 0 })
+        );
+        assert_eq!(
+            TableMode::with_table_checked(&TOO_MANY),
+            Err(TableError::TooManyStates)
+        );
+    }
+
+    #[test]
+    fn trace_captures_every_transition() {
+        let mut traced = Vec::new();
+        let mut mode = TableMode::new(StepMode::Half)
+            .with_trace(|prev, next, dir| traced.push((prev, next, dir)));
+        count_steps(&mut mode, &CW_CYCLE, 1);
+        let mut mode = mode.without_trace();
+        // R_START → R_CW_BEGIN_H → R_START_M → R_CW_BEGIN_M → R_START
+        assert_eq!(
+            traced,
+            vec![
+                (0, 2, Direction::None),
+                (2, 3, Direction::Clockwise),
+                (3, 4, Direction::None),
+                (4, 0, Direction::Clockwise),
+            ]
+        );
+        // Still at rest once the hook is removed
+        assert_eq!(mode.update_step(true, true), Step::Idle);
+    }
+
+    #[test]
+    fn set_step_mode_resets_table_state() {
+        let mut mode = TableMode::default();
+        mode.update(false, true);
+        assert_ne!(mode.table_state, R_START);
+        mode.set_step_mode(StepMode::Half);
+        assert_eq!(mode.table_state, R_START);
+        assert_eq!(count_steps(&mut mode, &CW_CYCLE, 1), 2);
+    }
+}